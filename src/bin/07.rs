@@ -1,8 +1,6 @@
-use std::{collections::HashMap, str::FromStr};
-
 advent_of_code::solution!(7);
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
 enum Rank {
     HighCard,
     OnePair,
@@ -13,112 +11,111 @@ enum Rank {
     FiveOfAKind,
 }
 
-struct RankParseError {}
-
-impl FromStr for Rank {
-    type Err = RankParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let mut m: HashMap<u8, usize> = HashMap::new();
-        let mut jokers = 0;
-        for b in s.as_bytes() {
-            if b == &b'j' {
-                jokers += 1;
-            } else {
-                *m.entry(*b).or_default() += 1;
-            }
-        }
-        let mut counts = m.values().clone().collect::<Vec<_>>();
-        counts.sort_by(|a, b| b.cmp(&a));
-        match (counts.as_slice(), jokers) {
-            (&[5], _) | (&[4], 1) | (&[3], 2) | (&[2], 3) | (&[1], 4) | (_, 5) => {
-                Ok(Self::FiveOfAKind)
-            }
-            (&[4, ..], _) | (&[3, ..], 1) | (&[2, ..], 2) | (&[1, ..], 3) | (_, 4) => {
-                Ok(Self::FourOfAKind)
-            }
-            (&[3, 2], _) | (&[2, 2], 1) => Ok(Self::FullHouse),
-            (&[3, ..], _) | (&[2, ..], 1) | (&[1, ..], 2) | (_, 3) => Ok(Self::ThreeOfAKind),
-            (&[2, 2, ..], _) => Ok(Self::TwoPair),
-            (&[2, ..], _) | (&[1, ..], 1) => Ok(Self::OnePair),
-            (&[1, ..], _) => Ok(Self::HighCard),
-            _ => Err(RankParseError {}),
+impl Rank {
+    /// Ranks a hand from the counts of its natural cards, largest first, and the number of
+    /// wildcards. Wildcards always do best by joining the largest group.
+    fn from_counts(counts: &[usize], wildcards: usize) -> Rank {
+        let largest = counts.first().copied().unwrap_or(0) + wildcards;
+        match (largest, counts.get(1).copied().unwrap_or(0)) {
+            (5.., _) => Rank::FiveOfAKind,
+            (4, _) => Rank::FourOfAKind,
+            (3, 2..) => Rank::FullHouse,
+            (3, _) => Rank::ThreeOfAKind,
+            (2, 2..) => Rank::TwoPair,
+            (2, _) => Rank::OnePair,
+            _ => Rank::HighCard,
         }
     }
 }
 
-#[derive(PartialEq, Eq, PartialOrd, Ord)]
+/// How hands of the same rank are ordered.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TieBreak {
+    /// Compare the cards one by one in the order they were dealt, as in Camel Cards.
+    AsDealt,
+    /// Compare the largest groups first and then the remaining cards from strongest, as in poker.
+    ByGroup,
+}
+
+/// A variant of the game: which cards exist, how they rank, and how ties are broken.
+#[derive(Debug, Clone, Copy)]
+struct Rules {
+    /// Every card, from weakest to strongest.
+    order: &'static str,
+    /// Cards that count as whatever makes the best hand.
+    wildcards: &'static str,
+    tie_break: TieBreak,
+}
+
+const CAMEL_CARDS: Rules = Rules {
+    order: "23456789TJQKA",
+    wildcards: "",
+    tie_break: TieBreak::AsDealt,
+};
+
+const JOKERS_WILD: Rules = Rules {
+    order: "J23456789TQKA",
+    wildcards: "J",
+    tie_break: TieBreak::AsDealt,
+};
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
 struct Hand {
     rank: Rank,
-    cards: Vec<u8>,
+    /// Card strengths in the order they are compared for ties.
+    strengths: Vec<usize>,
     bid: u32,
 }
 
-struct HandParseError {}
-
-impl FromStr for Hand {
-    type Err = HandParseError;
-
-    fn from_str(s: &str) -> Result<Self, Self::Err> {
-        let (cards_s, bid) = s.split_once(" ").unwrap();
-        let cards = cards_s
-            .as_bytes()
-            .iter()
-            .map(|b| match b {
-                b'j' => 0,
-                b'1' => 1,
-                b'2' => 2,
-                b'3' => 3,
-                b'4' => 4,
-                b'5' => 5,
-                b'6' => 6,
-                b'7' => 7,
-                b'8' => 8,
-                b'9' => 9,
-                b'T' => 10,
-                b'J' => 11,
-                b'Q' => 12,
-                b'K' => 13,
-                b'A' => 14,
-                _ => unreachable!(),
-            })
-            .collect::<Vec<_>>();
-        Ok(Self {
-            rank: Rank::from_str(cards_s).ok().unwrap(),
-            cards,
-            bid: bid.parse().unwrap(),
+impl Rules {
+    /// Parses a `cards bid` line, or [`None`] if it contains a card these rules don't know.
+    fn hand(&self, line: &str) -> Option<Hand> {
+        let (cards, bid) = line.split_once(' ')?;
+        let mut strengths = cards
+            .chars()
+            .map(|c| self.order.find(c))
+            .collect::<Option<Vec<_>>>()?;
+
+        let mut counts = vec![0; self.order.len()];
+        let mut wildcards = 0;
+        for c in cards.chars() {
+            if self.wildcards.contains(c) {
+                wildcards += 1;
+            } else {
+                counts[self.order.find(c)?] += 1;
+            }
+        }
+
+        if self.tie_break == TieBreak::ByGroup {
+            strengths.sort_by_key(|s| std::cmp::Reverse((counts[*s], *s)));
+        }
+
+        counts.sort_unstable_by(|a, b| b.cmp(a));
+
+        Some(Hand {
+            rank: Rank::from_counts(&counts, wildcards),
+            strengths,
+            bid: bid.parse().ok()?,
         })
     }
+
+    /// The total winnings: every bid multiplied by its hand's position from the weakest.
+    fn winnings(&self, input: &str) -> Option<u32> {
+        let mut hands = input
+            .lines()
+            .map(|l| self.hand(l))
+            .collect::<Option<Vec<Hand>>>()?;
+        hands.sort();
+        Some((1..).zip(&hands).map(|(i, h)| i * h.bid).sum())
+    }
 }
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut hands = input
-        .lines()
-        .map(|l| Hand::from_str(l).ok().unwrap())
-        .collect::<Vec<Hand>>();
-    hands.sort();
-    Some(
-        hands
-            .iter()
-            .enumerate()
-            .map(|(i, h)| (i + 1) as u32 * h.bid)
-            .sum(),
-    )
+    CAMEL_CARDS.winnings(input)
 }
 
 pub fn part_two(input: &str) -> Option<u32> {
-    let mut hands = input
-        .lines()
-        .map(|l| Hand::from_str(&l.replace("J", "j")).ok().unwrap())
-        .collect::<Vec<Hand>>();
-    hands.sort();
-    Some(
-        hands
-            .iter()
-            .enumerate()
-            .map(|(i, h)| (i + 1) as u32 * h.bid)
-            .sum(),
-    )
+    JOKERS_WILD.winnings(input)
 }
 
 #[cfg(test)]
@@ -126,14 +123,25 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6440));
+    fn test_rank_with_wildcards() {
+        let rank = |cards: &str| JOKERS_WILD.hand(&format!("{cards} 1")).unwrap().rank;
+        assert_eq!(rank("JJJJJ"), Rank::FiveOfAKind);
+        assert_eq!(rank("2JJJ3"), Rank::FourOfAKind);
+        assert_eq!(rank("22J33"), Rank::FullHouse);
+        assert_eq!(rank("2J345"), Rank::OnePair);
+        assert_eq!(rank("23456"), Rank::HighCard);
+        assert!(CAMEL_CARDS.hand("2345X 1").is_none());
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(5905));
+    fn test_tie_break_by_group() {
+        let poker = Rules {
+            tie_break: TieBreak::ByGroup,
+            ..CAMEL_CARDS
+        };
+        // dealt in order the early king wins, but the pair of threes beats the pair of twos.
+        let (threes, kings) = ("23A3K 1", "2KA2Q 1");
+        assert!(CAMEL_CARDS.hand(threes) < CAMEL_CARDS.hand(kings));
+        assert!(poker.hand(threes) > poker.hand(kings));
     }
 }
@@ -1,81 +1,340 @@
-use std::collections::HashMap;
+use advent_of_code::cycles::find_cycle;
+use advent_of_code::dot::Dot;
+use advent_of_code::graph::Graph;
+use advent_of_code::math::{crt, CrtError};
+use advent_of_code::parse::{ParseError, Span};
+use advent_of_code::template::runner::report_error;
+use std::collections::HashSet;
+use std::fmt::Display;
 
-advent_of_code::solution!(8);
+advent_of_code::solution!(8, dot = network);
+
+/// Reads the route and the network below it. The route must be a non-empty string of `L` and
+/// `R` turns, and every node must be defined once, on a line like `AAA = (BBB, CCC)`.
+fn parse(input: &str) -> Result<(&str, Graph<'_>), ParseError> {
+    let input = Span::new(input);
+    let mut sections = input.sections();
+    let route = sections
+        .next()
+        .ok_or_else(|| input.error("expecting a route"))?;
+    if let Some(i) = route.as_str().find(|c| c != 'L' && c != 'R') {
+        return Err(route
+            .slice(i, i + 1)
+            .unwrap_or(route)
+            .error("expecting `L` or `R`, and a blank line before the network"));
+    }
+    let network = sections
+        .next()
+        .ok_or_else(|| route.error("expecting the network after a blank line"))?;
+
+    let nodes = network
+        .lines()
+        .map(|l| {
+            let (source, dest) = l.split_once(" = ")?;
+            let (left, right) = dest.strip_prefix("(")?.split_once(", ")?;
+            let (right, _) = right.split_once(")")?;
+            Ok((source, [left, right]))
+        })
+        .collect::<Result<Vec<_>, ParseError>>()?;
+
+    let mut defined = HashSet::new();
+    for (source, _) in &nodes {
+        if !defined.insert(source.as_str()) {
+            return Err(source.error(format!("node `{source}` is defined twice")));
+        }
+    }
+    if let Some(undefined) = nodes
+        .iter()
+        .flat_map(|(_, dest)| dest)
+        .find(|n| !defined.contains(n.as_str()))
+    {
+        return Err(undefined.error(format!("node `{undefined}` is never defined")));
+    }
+
+    let graph = Graph::from_adjacency(
+        nodes
+            .iter()
+            .map(|(source, dest)| (source.as_str(), dest.map(|n| n.as_str()))),
+    );
+    Ok((route.as_str(), graph))
+}
+
+/// Follows one instruction, the first neighbour is left and the second right.
+fn step(graph: &Graph, loc: usize, c: char) -> usize {
+    graph.neighbors(loc)[usize::from(c == 'R')]
+}
+
+/// The network with left and right turns as edge labels, and the ghosts' start and end nodes
+/// highlighted.
+pub fn network(input: &str) -> Dot {
+    let mut dot = Dot::digraph();
+    let graph = match parse(input) {
+        Ok((_, graph)) => graph,
+        Err(err) => {
+            eprintln!("{err}");
+            return dot;
+        }
+    };
+    for id in graph.nodes() {
+        match graph.neighbors(id) {
+            [left, right] if left == right => {
+                dot.labelled_edge(graph.label(id), graph.label(*left), "LR");
+            }
+            [left, right] => {
+                dot.labelled_edge(graph.label(id), graph.label(*left), "L")
+                    .labelled_edge(graph.label(id), graph.label(*right), "R");
+            }
+            _ => {
+                dot.node(graph.label(id));
+            }
+        }
+    }
+    let ending = |suffix| {
+        graph
+            .nodes()
+            .map(|n| graph.label(n))
+            .filter(move |l| l.ends_with(suffix))
+    };
+    dot.highlight(ending('A'), "palegreen")
+        .highlight(ending('Z'), "salmon");
+    dot
+}
+
+/// Counts the steps from `AAA` to `ZZZ`.
+fn steps_to_zzz(route: &str, graph: &Graph) -> Result<u32, GhostError> {
+    let start = graph.id("AAA").ok_or(GhostError::MissingNode("AAA"))?;
+    let dest = graph.id("ZZZ").ok_or(GhostError::MissingNode("ZZZ"))?;
+    // once every node has been left from every route position, the walk only repeats itself.
+    let states = graph.len() * route.len();
+    let steps = route
+        .chars()
+        .cycle()
+        .take(states)
+        .scan(start, |loc, c| {
+            *loc = step(graph, *loc, c);
+            Some(*loc)
+        })
+        .position(|loc| loc == dest)
+        .ok_or(GhostError::NeverArrives)?;
+    u32::try_from(steps + 1).map_err(|_| GhostError::Overflow)
+}
 
 pub fn part_one(input: &str) -> Option<u32> {
-    let mut paths = HashMap::new();
-    let (route, path_str) = input.split_once("\n\n").unwrap();
-    path_str.lines().for_each(|l| {
-        let source = l.get(0..3).unwrap();
-        let ldest = l.get(7..10).unwrap();
-        let rdest = l.get(12..15).unwrap();
-        paths.insert((source, &'L'), ldest);
-        paths.insert((source, &'R'), rdest);
-    });
-    let mut loc = "AAA";
-    let mut steps = 0;
-    const DEST: &str = "ZZZ";
-    while loc != DEST {
-        route.chars().for_each(|c| {
-            loc = paths.get(&(loc, &c)).unwrap();
-            steps += 1;
-        });
-    }
-    Some(steps)
+    let (route, graph) = parse(input).map_err(report_error).ok()?;
+    steps_to_zzz(route, &graph)
+        .map_err(|err| report_unsolvable("one", &err))
+        .ok()
 }
 
-pub fn part_two(input: &str) -> Option<u64> {
-    let mut paths = HashMap::new();
-    let (route, path_str) = input.split_once("\n\n").unwrap();
-    path_str.lines().for_each(|l| {
-        let source = l.get(0..3).unwrap();
-        let ldest = l.get(7..10).unwrap();
-        let rdest = l.get(12..15).unwrap();
-        paths.insert((source, &'L'), ldest);
-        paths.insert((source, &'R'), rdest);
-    });
-    let mut shortpaths = HashMap::new();
-    let mut loc;
-    for key in paths.keys() {
-        loc = key.0;
-        route.chars().for_each(|c| {
-            loc = paths.get(&(loc, &c)).unwrap();
-        });
-        shortpaths.insert(key.0, loc);
-    }
-    let locs = paths
-        .keys()
-        .filter(|s| s.0.ends_with("A") && s.1 == &'L')
-        .map(|s| s.0)
-        .collect::<Vec<_>>();
-    // turns out this works because these are all primes (including the path)
-    // but if they were not it would be necessary to find the set of GCDs
-    let soln = locs.iter().map(|l| {
-        let mut iter = 0;
-        let mut loc = l;
-        while !loc.ends_with("Z") {
-            loc = shortpaths.get(loc).unwrap();
-            iter += 1;
+/// Reasons why walking the network never ends on `ZZZ`, or the ghosts never all stand on
+/// nodes ending in `Z` at once.
+#[derive(Debug, PartialEq, Eq)]
+enum GhostError {
+    MissingNode(&'static str),
+    /// The walk from `AAA` loops forever without passing `ZZZ`.
+    NeverArrives,
+    NoGhosts,
+    /// The ghost starting at this node loops forever without passing a `Z` node.
+    NeverFinishes(String),
+    /// Every ghost passes `Z` nodes, but never on the same step.
+    NeverAligned,
+    Overflow,
+}
+
+impl Display for GhostError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GhostError::MissingNode(label) => write!(f, "there is no node {label}"),
+            GhostError::NeverArrives => {
+                write!(f, "the walk from AAA loops forever without reaching ZZZ")
+            }
+            GhostError::NoGhosts => write!(f, "no node ends in `A`, so there are no ghosts"),
+            GhostError::NeverFinishes(start) => write!(
+                f,
+                "the ghost starting at {start} loops forever without reaching a node ending in `Z`"
+            ),
+            GhostError::NeverAligned => write!(
+                f,
+                "the ghosts reach nodes ending in `Z`, but never all on the same step"
+            ),
+            GhostError::Overflow => write!(f, "the number of steps does not fit into a u64"),
         }
-        iter as u64
-    });
-    // need to multiply by the path as well since we made a shortcode lookup
-    Some(soln.product::<u64>() * route.len() as u64)
+    }
+}
+
+/// Finds the first step on which every ghost stands on a node ending in `Z`.
+///
+/// A ghost's state is its node and its position in the route, so its walk repeats after a
+/// lead-in. Past the longest lead-in, ghost `g` is on a `Z` node exactly on the steps
+/// `t ≡ prefix + offset (mod period)` for each `Z` offset in its loop, and all ghosts are
+/// combined with the Chinese Remainder Theorem.
+fn ghost_steps(route: &str, graph: &Graph) -> Result<u64, GhostError> {
+    let turns: Vec<char> = route.chars().collect();
+    let is_end: Vec<bool> = graph
+        .nodes()
+        .map(|n| graph.label(n).ends_with('Z'))
+        .collect();
+
+    let ghosts: Vec<_> = graph
+        .nodes()
+        .filter(|n| graph.label(*n).ends_with('A'))
+        .map(|start| {
+            let cycle = find_cycle((start, 0), |&(loc, i)| {
+                (step(graph, loc, turns[i]), (i + 1) % turns.len())
+            });
+            (start, cycle)
+        })
+        .collect();
+
+    if ghosts.is_empty() {
+        return Err(GhostError::NoGhosts);
+    }
+
+    // until every ghost has entered its loop, just check each step.
+    let lead_in = ghosts.iter().map(|(_, c)| c.prefix).max().unwrap_or(0);
+    if let Some(t) = (0..lead_in).find(|t| ghosts.iter().all(|(_, c)| is_end[c.state_at(*t).0])) {
+        return Ok(t as u64);
+    }
+
+    let mut solutions = vec![(0, 1)];
+    for (start, cycle) in &ghosts {
+        let period = cycle.period as u64;
+        let congruences: Vec<(u64, u64)> = (cycle.prefix..cycle.prefix + cycle.period)
+            .filter(|i| is_end[cycle.states[*i].0])
+            .map(|i| (i as u64 % period, period))
+            .collect();
+
+        if congruences.is_empty() {
+            return Err(GhostError::NeverFinishes(graph.label(*start).to_string()));
+        }
+
+        // usually there is a single `Z` per loop, but every combination is a candidate.
+        let mut merged = vec![];
+        for solution in &solutions {
+            for congruence in &congruences {
+                match crt([*solution, *congruence]) {
+                    Ok(s) => merged.push(s),
+                    Err(CrtError::NoSolution) => {}
+                    Err(CrtError::Overflow) => return Err(GhostError::Overflow),
+                }
+            }
+        }
+        merged.sort_unstable();
+        merged.dedup();
+        solutions = merged;
+    }
+
+    // the first solution at or after the lead-in.
+    let lead_in = lead_in as u64;
+    solutions
+        .into_iter()
+        .map(|(x, m)| {
+            if x >= lead_in {
+                Some(x)
+            } else {
+                (lead_in - x).div_ceil(m).checked_mul(m)?.checked_add(x)
+            }
+        })
+        .min()
+        .ok_or(GhostError::NeverAligned)?
+        .ok_or(GhostError::Overflow)
+}
+
+fn report_unsolvable(part: &str, err: &GhostError) {
+    report_error(format!("Day 8 part {part} is unsolvable: {err}."));
+}
+
+pub fn part_two(input: &str) -> Option<u64> {
+    let (route, graph) = parse(input).map_err(report_error).ok()?;
+    ghost_steps(route, &graph)
+        .map_err(|err| report_unsolvable("two", &err))
+        .ok()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    fn ghosts(input: &str) -> Result<u64, GhostError> {
+        let (route, graph) = parse(input).unwrap();
+        ghost_steps(route, &graph)
+    }
+
+    fn parse_error(input: &str) -> (usize, usize, String) {
+        let err = parse(input).err().unwrap();
+        (err.line, err.column, err.message)
+    }
+
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+    fn test_ghosts_with_lead_in_and_shared_factors() {
+        // 11A reaches a Z on every even step, 22A on every fourth, both after a lead-in.
+        let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+            22A = (22B, 22B)\n22B = (22C, 22C)\n22C = (22D, 22D)\n22D = (22Z, 22Z)\n\
+            22Z = (22B, 22B)\n";
+        assert_eq!(ghosts(input), Ok(4));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(6));
+    fn test_ghosts_finishing_in_lead_in() {
+        let input = "LR\n\n11A = (11Z, 11Z)\n11Z = (11B, 11B)\n11B = (11B, 11B)\n\
+            22A = (22Z, 22B)\n22Z = (22B, 22B)\n22B = (22B, 22B)\n";
+        assert_eq!(ghosts(input), Ok(1));
+    }
+
+    #[test]
+    fn test_unsolvable_ghosts() {
+        // 11A is on a Z on even steps, 22A on odd ones.
+        let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+            22A = (22Z, 22Z)\n22Z = (22B, 22B)\n22B = (22Z, 22Z)\n";
+        assert_eq!(ghosts(input), Err(GhostError::NeverAligned));
+
+        let input = "L\n\n11A = (11B, 11B)\n11B = (11Z, 11Z)\n11Z = (11B, 11B)\n\
+            33A = (33B, 33B)\n33B = (33B, 33B)\n";
+        assert_eq!(ghosts(input), Err(GhostError::NeverFinishes("33A".into())));
+    }
+
+    #[test]
+    fn test_network() {
+        let dot = network(&advent_of_code::template::read_file("examples", DAY)).to_string();
+        assert!(dot.contains("\"AAA\" -> \"BBB\" [label=\"LR\"];"));
+        assert!(dot.contains("\"BBB\" -> \"ZZZ\" [label=\"R\"];"));
+        assert!(dot.contains("\"ZZZ\" [style=filled, fillcolor=\"salmon\"];"));
+    }
+
+    #[test]
+    fn test_malformed_networks() {
+        let bad_route = "expecting `L` or `R`, and a blank line before the network";
+        assert_eq!(
+            parse_error("LR\nAAA = (ZZZ, ZZZ)\n"),
+            (1, 3, bad_route.into())
+        );
+        assert_eq!(
+            parse_error("\n\nAAA = (ZZZ, ZZZ)\n"),
+            (3, 1, bad_route.into())
+        );
+        assert_eq!(
+            parse_error("LR\n\nAAA = (ZZZ, BBB)\nZZZ = (ZZZ, ZZZ)\n"),
+            (3, 13, "node `BBB` is never defined".into())
+        );
+        assert_eq!(
+            parse_error("LR\n\nAAA = (ZZZ, ZZZ\n"),
+            (3, 13, "expecting `)`".into())
+        );
+        assert_eq!(part_one("LR\n\nAAA = (ZZZ, BBB\n"), None);
+        assert_eq!(part_two("LR\n\nAAA = (ZZZ, BBB\n"), None);
+    }
+
+    #[test]
+    fn test_unreachable_zzz() {
+        let input = "LR\n\nAAA = (BBB, BBB)\nBBB = (AAA, AAA)\nZZZ = (ZZZ, ZZZ)\n";
+        let (route, graph) = parse(input).unwrap();
+        assert_eq!(steps_to_zzz(route, &graph), Err(GhostError::NeverArrives));
+        assert_eq!(part_one(input), None);
+
+        let (route, graph) = parse("L\n\nAAA = (AAA, AAA)\n").unwrap();
+        assert_eq!(
+            steps_to_zzz(route, &graph),
+            Err(GhostError::MissingNode("ZZZ"))
+        );
     }
 }
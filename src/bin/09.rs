@@ -1,64 +1,59 @@
-advent_of_code::solution!(9);
+use advent_of_code::poly::{Poly, PolyError};
 
-fn diff(v: Vec<i64>) -> Vec<i64> {
-    v.windows(2)
-        .map(|w| match w {
-            &[w0, w1] => w1 - w0,
-            _ => 0,
-        })
-        .collect()
-}
-
-// don't need to do the final diff, can just diff until constant
-fn is_constant(v: Vec<i64>) -> bool {
-    match v.first() {
-        None => false,
-        Some(f) => v.iter().all(|i| i == f),
-    }
-}
+advent_of_code::solution!(9, scale = generate_input);
 
-// val is just sum of all the last vals we find
-fn find_next(v: Vec<i64>) -> i64 {
-    let mut differential: Vec<i64>;
-    let mut lasts = vec![*v.last().unwrap()];
-    differential = v;
-    while !is_constant(differential.clone()) {
-        let diff_tmp = diff(differential);
-        let last_val = diff_tmp.clone().last().unwrap().clone();
-        lasts.push(last_val);
-        differential = diff_tmp.clone();
-    }
-    lasts.iter().sum()
-}
-
-pub fn part_one(input: &str) -> Option<i64> {
-    let soln: i64 = input
+/// Fits a polynomial to every sequence and sums their values at `index(len)`.
+/// Sequences too short to confirm their polynomial are extrapolated from their last
+/// difference row, which is assumed to stay constant. Returns [`None`] if a value is not an
+/// integer or the sum overflows.
+fn extrapolate(input: &str, index: impl Fn(i64) -> i64) -> Option<i64> {
+    input
         .lines()
         .map(|l| {
             let seq = l
                 .split_whitespace()
-                .map(|n| n.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
-            find_next(seq)
+                .map(|n| n.parse::<i64>().ok())
+                .collect::<Option<Vec<_>>>()?;
+            let poly = match Poly::fit(&seq) {
+                Err(PolyError::NotPolynomial) => Poly::interpolate(&seq).ok()?,
+                fitted => fitted.ok()?,
+            };
+            i64::try_from(poly.eval(index(seq.len() as i64))?).ok()
         })
-        .sum();
-    Some(soln)
+        .try_fold(0i64, |sum, value| sum.checked_add(value?))
 }
 
-pub fn part_two(input: &str) -> Option<i64> {
-    let soln: i64 = input
-        .lines()
-        .map(|l| {
-            let mut seq = l
-                .split_whitespace()
-                .map(|n| n.parse::<i64>().unwrap())
-                .collect::<Vec<_>>();
-            // literally just reverse the sequences and do same thing
-            seq.reverse();
-            find_next(seq)
+/// Generates `size` sequences of 21 values, each following a polynomial of degree 4 or less.
+pub fn generate_input(size: usize) -> String {
+    let mut seed: i64 = 9;
+    let mut next = || {
+        seed = (seed * 1_103_515_245 + 12345) % 2_147_483_648;
+        seed % 21 - 10
+    };
+    (0..size)
+        .map(|_| {
+            let coefficients = [next(), next(), next(), next(), next() % 3];
+            (0..21)
+                .map(|x: i64| {
+                    coefficients
+                        .iter()
+                        .rev()
+                        .fold(0, |acc, c| acc * x + c)
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join(" ")
         })
-        .sum();
-    Some(soln)
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+pub fn part_one(input: &str) -> Option<i64> {
+    extrapolate(input, |len| len)
+}
+
+pub fn part_two(input: &str) -> Option<i64> {
+    extrapolate(input, |_| -1)
 }
 
 #[cfg(test)]
@@ -66,14 +61,15 @@ mod tests {
     use super::*;
 
     #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(114));
+    fn test_unconfirmed_sequences() {
+        let input = "5\n1 3 6\n";
+        assert_eq!(part_one(input), Some(5 + 10));
+        assert_eq!(part_two(input), Some(5));
     }
 
     #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, Some(2));
+    fn test_malformed_values() {
+        assert_eq!(part_one("0 3 6 9\n1 x 3\n"), None);
+        assert_eq!(part_two("0 3 6 9 12 15\n1 2 3.5\n"), None);
     }
 }
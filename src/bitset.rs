@@ -0,0 +1,443 @@
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::iter::from_fn;
+use std::ops::{BitAnd, BitOr, BitXor, Sub};
+
+macro_rules! fixed_bitset {
+    ($(#[$meta:meta])* $name:ident, $word:ty) => {
+        $(#[$meta])*
+        #[derive(Clone, Copy, Default, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name($word);
+
+        impl $name {
+            /// The number of values this set can hold, i.e. values `0..CAPACITY`.
+            pub const CAPACITY: usize = <$word>::BITS as usize;
+
+            pub const fn new() -> Self {
+                Self(0)
+            }
+
+            /// Creates a set from its bit representation, bit `i` standing for value `i`.
+            pub const fn from_bits(bits: $word) -> Self {
+                Self(bits)
+            }
+
+            pub const fn bits(&self) -> $word {
+                self.0
+            }
+
+            /// Adds `value`, returning `true` if it was not present.
+            ///
+            /// # Panics
+            /// Panics if `value` is not below [`Self::CAPACITY`].
+            pub fn insert(&mut self, value: usize) -> bool {
+                assert!(
+                    value < Self::CAPACITY,
+                    "value {value} does not fit into a {}",
+                    stringify!($name)
+                );
+                let absent = !self.contains(value);
+                self.0 |= 1 << value;
+                absent
+            }
+
+            /// Removes `value`, returning `true` if it was present.
+            pub fn remove(&mut self, value: usize) -> bool {
+                let present = self.contains(value);
+                if present {
+                    self.0 &= !(1 << value);
+                }
+                present
+            }
+
+            pub fn contains(&self, value: usize) -> bool {
+                value < Self::CAPACITY && self.0 >> value & 1 == 1
+            }
+
+            /// The number of values in the set.
+            pub fn len(&self) -> usize {
+                self.0.count_ones() as usize
+            }
+
+            pub fn is_empty(&self) -> bool {
+                self.0 == 0
+            }
+
+            pub fn clear(&mut self) {
+                self.0 = 0;
+            }
+
+            pub fn union(&self, other: &Self) -> Self {
+                Self(self.0 | other.0)
+            }
+
+            pub fn intersection(&self, other: &Self) -> Self {
+                Self(self.0 & other.0)
+            }
+
+            pub fn difference(&self, other: &Self) -> Self {
+                Self(self.0 & !other.0)
+            }
+
+            pub fn symmetric_difference(&self, other: &Self) -> Self {
+                Self(self.0 ^ other.0)
+            }
+
+            pub fn is_subset(&self, other: &Self) -> bool {
+                self.0 & !other.0 == 0
+            }
+
+            pub fn is_disjoint(&self, other: &Self) -> bool {
+                self.0 & other.0 == 0
+            }
+
+            /// The smallest value in the set.
+            pub fn first(&self) -> Option<usize> {
+                (!self.is_empty()).then(|| self.0.trailing_zeros() as usize)
+            }
+
+            /// Iterates the values in ascending order.
+            pub fn iter(&self) -> impl Iterator<Item = usize> {
+                let mut bits = self.0;
+                from_fn(move || {
+                    if bits == 0 {
+                        return None;
+                    }
+                    let value = bits.trailing_zeros() as usize;
+                    bits &= bits - 1;
+                    Some(value)
+                })
+            }
+        }
+
+        impl Debug for $name {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_set().entries(self.iter()).finish()
+            }
+        }
+
+        impl FromIterator<usize> for $name {
+            fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+                let mut set = Self::new();
+                set.extend(iter);
+                set
+            }
+        }
+
+        impl Extend<usize> for $name {
+            fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+                for value in iter {
+                    self.insert(value);
+                }
+            }
+        }
+
+        impl BitOr for $name {
+            type Output = Self;
+
+            fn bitor(self, rhs: Self) -> Self {
+                self.union(&rhs)
+            }
+        }
+
+        impl BitAnd for $name {
+            type Output = Self;
+
+            fn bitand(self, rhs: Self) -> Self {
+                self.intersection(&rhs)
+            }
+        }
+
+        impl BitXor for $name {
+            type Output = Self;
+
+            fn bitxor(self, rhs: Self) -> Self {
+                self.symmetric_difference(&rhs)
+            }
+        }
+
+        impl Sub for $name {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self {
+                self.difference(&rhs)
+            }
+        }
+    };
+}
+
+fixed_bitset!(
+    /// A set of values in `0..64` stored in a single `u64`.
+    ///
+    /// ```
+    /// # use advent_of_code::bitset::BitSet64;
+    /// let a: BitSet64 = [1, 5, 7].into_iter().collect();
+    /// let b: BitSet64 = [5, 7, 9].into_iter().collect();
+    /// assert_eq!((a & b).len(), 2);
+    /// assert_eq!((a | b).iter().collect::<Vec<_>>(), vec![1, 5, 7, 9]);
+    /// ```
+    BitSet64,
+    u64
+);
+
+fixed_bitset!(
+    /// A set of values in `0..128` stored in a single `u128`, see [`BitSet64`].
+    BitSet128,
+    u128
+);
+
+/* -------------------------------------------------------------------------- */
+
+/// A set of small non-negative integers that grows to fit the largest value inserted.
+#[derive(Clone, Default)]
+pub struct BitSet {
+    words: Vec<u64>,
+}
+
+impl BitSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates an empty set that holds values below `capacity` without reallocating.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            words: Vec::with_capacity(capacity.div_ceil(64)),
+        }
+    }
+
+    /// The words without trailing zeros, so that equal sets compare and hash equally.
+    fn trimmed(&self) -> &[u64] {
+        let len = self
+            .words
+            .iter()
+            .rposition(|w| *w != 0)
+            .map_or(0, |i| i + 1);
+        &self.words[..len]
+    }
+
+    fn word(&self, i: usize) -> u64 {
+        self.words.get(i).copied().unwrap_or(0)
+    }
+
+    /// Adds `value`, returning `true` if it was not present.
+    pub fn insert(&mut self, value: usize) -> bool {
+        let (i, bit) = (value / 64, value % 64);
+        if i >= self.words.len() {
+            self.words.resize(i + 1, 0);
+        }
+        let absent = self.words[i] >> bit & 1 == 0;
+        self.words[i] |= 1 << bit;
+        absent
+    }
+
+    /// Removes `value`, returning `true` if it was present.
+    pub fn remove(&mut self, value: usize) -> bool {
+        let present = self.contains(value);
+        if present {
+            self.words[value / 64] &= !(1 << (value % 64));
+        }
+        present
+    }
+
+    pub fn contains(&self, value: usize) -> bool {
+        self.word(value / 64) >> (value % 64) & 1 == 1
+    }
+
+    /// The number of values in the set.
+    pub fn len(&self) -> usize {
+        self.words.iter().map(|w| w.count_ones() as usize).sum()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.iter().all(|w| *w == 0)
+    }
+
+    pub fn clear(&mut self) {
+        self.words.clear();
+    }
+
+    fn zip_with(&self, other: &Self, f: impl Fn(u64, u64) -> u64) -> Self {
+        let len = self.words.len().max(other.words.len());
+        Self {
+            words: (0..len).map(|i| f(self.word(i), other.word(i))).collect(),
+        }
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a | b)
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & b)
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a & !b)
+    }
+
+    pub fn symmetric_difference(&self, other: &Self) -> Self {
+        self.zip_with(other, |a, b| a ^ b)
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.words
+            .iter()
+            .enumerate()
+            .all(|(i, w)| w & !other.word(i) == 0)
+    }
+
+    pub fn is_disjoint(&self, other: &Self) -> bool {
+        self.words.iter().zip(&other.words).all(|(a, b)| a & b == 0)
+    }
+
+    /// The smallest value in the set.
+    pub fn first(&self) -> Option<usize> {
+        self.iter().next()
+    }
+
+    /// Iterates the values in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = usize> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, word)| {
+            let mut bits = *word;
+            from_fn(move || {
+                if bits == 0 {
+                    return None;
+                }
+                let value = i * 64 + bits.trailing_zeros() as usize;
+                bits &= bits - 1;
+                Some(value)
+            })
+        })
+    }
+}
+
+impl PartialEq for BitSet {
+    fn eq(&self, other: &Self) -> bool {
+        self.trimmed() == other.trimmed()
+    }
+}
+
+impl Eq for BitSet {}
+
+impl Hash for BitSet {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.trimmed().hash(state);
+    }
+}
+
+impl Debug for BitSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.iter()).finish()
+    }
+}
+
+impl FromIterator<usize> for BitSet {
+    fn from_iter<I: IntoIterator<Item = usize>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl Extend<usize> for BitSet {
+    fn extend<I: IntoIterator<Item = usize>>(&mut self, iter: I) {
+        for value in iter {
+            self.insert(value);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BitSet, BitSet128, BitSet64};
+    use std::collections::HashSet;
+
+    #[test]
+    fn fixed_insert_remove() {
+        let mut set = BitSet64::new();
+        assert!(set.insert(3));
+        assert!(!set.insert(3));
+        assert!(set.insert(63));
+        assert!(set.contains(63));
+        assert!(!set.contains(64));
+        assert_eq!(set.len(), 2);
+        assert_eq!(set.first(), Some(3));
+        assert!(set.remove(3));
+        assert!(!set.remove(3));
+        assert_eq!(set.bits(), 1 << 63);
+        assert_eq!(format!("{set:?}"), "{63}");
+    }
+
+    #[test]
+    #[should_panic]
+    fn fixed_out_of_range_panics() {
+        BitSet64::new().insert(64);
+    }
+
+    #[test]
+    fn fixed_set_algebra() {
+        let a: BitSet128 = [1, 64, 100, 127].into_iter().collect();
+        let b: BitSet128 = [1, 2, 100].into_iter().collect();
+        assert_eq!((a & b).iter().collect::<Vec<_>>(), vec![1, 100]);
+        assert_eq!((a | b).len(), 5);
+        assert_eq!((a - b).iter().collect::<Vec<_>>(), vec![64, 127]);
+        assert_eq!((a ^ b).iter().collect::<Vec<_>>(), vec![2, 64, 127]);
+        assert!((a & b).is_subset(&a));
+        assert!((a - b).is_disjoint(&b));
+    }
+
+    #[test]
+    fn growable_matches_hash_set() {
+        let a_values = [0, 5, 64, 65, 200, 1000];
+        let b_values = [5, 65, 66, 1000, 4000];
+        let (a, b): (BitSet, BitSet) = (
+            a_values.into_iter().collect(),
+            b_values.into_iter().collect(),
+        );
+        let (ha, hb): (HashSet<usize>, HashSet<usize>) = (
+            a_values.into_iter().collect(),
+            b_values.into_iter().collect(),
+        );
+
+        let sorted = |set: &HashSet<usize>| {
+            let mut values: Vec<_> = set.iter().copied().collect();
+            values.sort_unstable();
+            values
+        };
+        let to_vec = |set: BitSet| set.iter().collect::<Vec<_>>();
+
+        assert_eq!(
+            to_vec(a.union(&b)),
+            sorted(&ha.union(&hb).copied().collect())
+        );
+        assert_eq!(
+            to_vec(a.intersection(&b)),
+            sorted(&ha.intersection(&hb).copied().collect())
+        );
+        assert_eq!(
+            to_vec(a.difference(&b)),
+            sorted(&ha.difference(&hb).copied().collect())
+        );
+        assert_eq!(
+            to_vec(a.symmetric_difference(&b)),
+            sorted(&ha.symmetric_difference(&hb).copied().collect())
+        );
+        assert_eq!(a.len(), 6);
+        assert!(!a.is_disjoint(&b));
+    }
+
+    #[test]
+    fn growable_equality_ignores_capacity() {
+        let mut a: BitSet = [3, 500].into_iter().collect();
+        let b: BitSet = [3].into_iter().collect();
+        assert_ne!(a, b);
+        a.remove(500);
+        assert_eq!(a, b);
+        assert!(b.is_subset(&a));
+        assert!(BitSet::with_capacity(1000).is_empty());
+        assert_eq!(BitSet::new(), BitSet::with_capacity(10));
+    }
+}
@@ -0,0 +1,191 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::iter::successors;
+
+/// The shape of the sequence `start, step(start), step(step(start)), ...` once it repeats:
+/// `prefix` states lead into a loop of `period` states.
+///
+/// ```
+/// # use advent_of_code::cycles::brent;
+/// // 0 → 1 → 2 → 3 → 4 → 2 → ...
+/// let cycle = brent(0, |x| if *x == 4 { 2 } else { x + 1 });
+/// assert_eq!((cycle.prefix, cycle.period), (2, 3));
+/// assert_eq!(*cycle.state_at(1_000_000_000), 4);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<S> {
+    /// The number of steps before the sequence enters the loop.
+    pub prefix: usize,
+    /// The length of the loop.
+    pub period: usize,
+    /// The first `prefix + period` states, starting with the start state.
+    pub states: Vec<S>,
+}
+
+impl<S> Cycle<S> {
+    /// The index into [`Cycle::states`] of the state after `steps` steps.
+    pub fn index_at(&self, steps: usize) -> usize {
+        if steps < self.prefix {
+            steps
+        } else {
+            self.prefix + (steps - self.prefix) % self.period
+        }
+    }
+
+    /// The state after `steps` steps, without simulating them.
+    pub fn state_at(&self, steps: usize) -> &S {
+        &self.states[self.index_at(steps)]
+    }
+
+    /// The states that repeat forever.
+    pub fn loop_states(&self) -> &[S] {
+        &self.states[self.prefix..]
+    }
+}
+
+fn collect_states<S: Clone>(
+    start: S,
+    mut step: impl FnMut(&S) -> S,
+    prefix: usize,
+    period: usize,
+) -> Cycle<S> {
+    Cycle {
+        prefix,
+        period,
+        states: successors(Some(start), |s| Some(step(s)))
+            .take(prefix + period)
+            .collect(),
+    }
+}
+
+/// Detects the cycle with Brent's algorithm, which only compares states and keeps two of
+/// them around while searching. Never returns if the sequence does not repeat.
+pub fn brent<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    // find the period by teleporting the tortoise to the hare at every power of two.
+    let mut power = 1;
+    let mut period = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == period {
+            tortoise = hare.clone();
+            power *= 2;
+            period = 0;
+        }
+        hare = step(&hare);
+        period += 1;
+    }
+
+    // with the hare one period ahead, both meet at the start of the loop.
+    let mut tortoise = start.clone();
+    let mut hare = start.clone();
+    for _ in 0..period {
+        hare = step(&hare);
+    }
+    let mut prefix = 0;
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    collect_states(start, step, prefix, period)
+}
+
+/// Detects the cycle with Floyd's tortoise and hare. Like [`brent`] it keeps only two states
+/// around while searching, but it usually takes more steps.
+pub fn floyd<S: Clone + PartialEq>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        hare = step(&hare);
+    }
+
+    let mut prefix = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        prefix += 1;
+    }
+
+    let mut period = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        period += 1;
+    }
+
+    collect_states(start, step, prefix, period)
+}
+
+/// Detects the cycle by remembering every state in a hash map. Steps exactly
+/// `prefix + period` times, which suits expensive steps on cheap-to-hash states.
+pub fn find_cycle<S: Clone + Eq + Hash>(start: S, mut step: impl FnMut(&S) -> S) -> Cycle<S> {
+    let mut seen = HashMap::new();
+    let mut states = vec![];
+    let mut state = start;
+
+    loop {
+        if let Some(&prefix) = seen.get(&state) {
+            return Cycle {
+                prefix,
+                period: states.len() - prefix,
+                states,
+            };
+        }
+        let next = step(&state);
+        seen.insert(state.clone(), states.len());
+        states.push(state);
+        state = next;
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{brent, find_cycle, floyd, Cycle};
+
+    fn step(x: &u32) -> u32 {
+        (x * x + 1) % 255
+    }
+
+    fn check(cycle: &Cycle<u32>, start: u32) {
+        assert_eq!(cycle.states.len(), cycle.prefix + cycle.period);
+        let mut state = start;
+        for steps in 0..1000 {
+            assert_eq!(*cycle.state_at(steps), state);
+            state = step(&state);
+        }
+    }
+
+    #[test]
+    fn algorithms_agree() {
+        for start in 0..255 {
+            let expected = find_cycle(start, step);
+            check(&expected, start);
+            assert_eq!(brent(start, step), expected);
+            assert_eq!(floyd(start, step), expected);
+        }
+    }
+
+    #[test]
+    fn lead_in_and_loop() {
+        let cycle = find_cycle(0, |x| if *x == 4 { 2 } else { x + 1 });
+        assert_eq!((cycle.prefix, cycle.period), (2, 3));
+        assert_eq!(cycle.states, vec![0, 1, 2, 3, 4]);
+        assert_eq!(cycle.loop_states(), &[2, 3, 4]);
+        assert_eq!(cycle.index_at(5), 2);
+        assert_eq!(*cycle.state_at(1_000_000_000), 4);
+    }
+
+    #[test]
+    fn fixed_point() {
+        let cycle = brent(7, |x| *x);
+        assert_eq!((cycle.prefix, cycle.period), (0, 1));
+        assert_eq!(*cycle.state_at(12), 7);
+    }
+}
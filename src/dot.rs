@@ -0,0 +1,171 @@
+use crate::graph::Graph;
+use std::fmt::Display;
+
+/// A graph in Graphviz's DOT language, to render with e.g. `dot -Tsvg graph.dot -o graph.svg`.
+///
+/// Nodes are named by their id and only need to be added explicitly to give them a label
+/// or to list them without edges.
+///
+/// ```
+/// # use advent_of_code::dot::Dot;
+/// let mut dot = Dot::digraph();
+/// dot.labelled_edge("AAA", "BBB", "L").highlight(["AAA"], "palegreen");
+/// assert_eq!(
+///     dot.to_string(),
+///     "digraph {\n    \"AAA\" -> \"BBB\" [label=\"L\"];\n    \"AAA\" [style=filled, fillcolor=\"palegreen\"];\n}\n"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Dot {
+    directed: bool,
+    /// Node ids with an optional label, in the order they were added.
+    nodes: Vec<(String, Option<String>)>,
+    edges: Vec<(String, String, Option<String>)>,
+    /// Subsets of nodes with the colour they are filled with. Later subsets win.
+    highlights: Vec<(Vec<String>, String)>,
+}
+
+impl Dot {
+    /// An empty graph with directed edges.
+    pub fn digraph() -> Self {
+        Self {
+            directed: true,
+            ..Self::default()
+        }
+    }
+
+    /// An empty graph with undirected edges.
+    pub fn graph() -> Self {
+        Self::default()
+    }
+
+    /// A directed graph with every node and edge of `graph`, named by their labels.
+    pub fn from_graph(graph: &Graph) -> Self {
+        let mut dot = Self::digraph();
+        for id in graph.nodes() {
+            dot.node(graph.label(id));
+        }
+        for (source, target) in graph.edges() {
+            dot.edge(graph.label(source), graph.label(target));
+        }
+        dot
+    }
+
+    pub fn node(&mut self, id: impl Display) -> &mut Self {
+        self.nodes.push((id.to_string(), None));
+        self
+    }
+
+    /// Adds a node that displays `label` instead of its id.
+    pub fn labelled_node(&mut self, id: impl Display, label: impl Display) -> &mut Self {
+        self.nodes.push((id.to_string(), Some(label.to_string())));
+        self
+    }
+
+    pub fn edge(&mut self, from: impl Display, to: impl Display) -> &mut Self {
+        self.edges.push((from.to_string(), to.to_string(), None));
+        self
+    }
+
+    pub fn labelled_edge(
+        &mut self,
+        from: impl Display,
+        to: impl Display,
+        label: impl Display,
+    ) -> &mut Self {
+        self.edges
+            .push((from.to_string(), to.to_string(), Some(label.to_string())));
+        self
+    }
+
+    /// Fills the given nodes with `colour`, any colour name or `#rrggbb` Graphviz accepts.
+    pub fn highlight<I>(&mut self, ids: I, colour: &str) -> &mut Self
+    where
+        I: IntoIterator,
+        I::Item: Display,
+    {
+        let ids = ids.into_iter().map(|id| id.to_string()).collect();
+        self.highlights.push((ids, colour.to_string()));
+        self
+    }
+}
+
+/// Quotes `s` as a DOT string.
+fn quote(s: &str) -> String {
+    format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+impl Display for Dot {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let (keyword, arrow) = if self.directed {
+            ("digraph", "->")
+        } else {
+            ("graph", "--")
+        };
+        writeln!(f, "{keyword} {{")?;
+        for (id, label) in &self.nodes {
+            match label {
+                Some(label) => writeln!(f, "    {} [label={}];", quote(id), quote(label))?,
+                None => writeln!(f, "    {};", quote(id))?,
+            }
+        }
+        for (from, to, label) in &self.edges {
+            write!(f, "    {} {arrow} {}", quote(from), quote(to))?;
+            match label {
+                Some(label) => writeln!(f, " [label={}];", quote(label))?,
+                None => writeln!(f, ";")?,
+            }
+        }
+        // repeated node statements add to the earlier attributes, so highlights come last.
+        for (ids, colour) in &self.highlights {
+            for id in ids {
+                writeln!(
+                    f,
+                    "    {} [style=filled, fillcolor={}];",
+                    quote(id),
+                    quote(colour)
+                )?;
+            }
+        }
+        writeln!(f, "}}")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Dot;
+    use crate::graph::Graph;
+
+    #[test]
+    fn renders_undirected_graphs() {
+        let mut dot = Dot::graph();
+        dot.labelled_node(1, "one").node(2).edge(1, 2);
+        assert_eq!(
+            dot.to_string(),
+            "graph {\n    \"1\" [label=\"one\"];\n    \"2\";\n    \"1\" -- \"2\";\n}\n"
+        );
+    }
+
+    #[test]
+    fn escapes_quotes() {
+        let mut dot = Dot::digraph();
+        dot.labelled_node("a\"b", "c\\d");
+        assert_eq!(
+            dot.to_string(),
+            "digraph {\n    \"a\\\"b\" [label=\"c\\\\d\"];\n}\n"
+        );
+    }
+
+    #[test]
+    fn converts_graphs() {
+        let graph = Graph::from_adjacency([("AAA", ["BBB", "ZZZ"])]);
+        let mut dot = Dot::from_graph(&graph);
+        dot.highlight(["AAA"], "green").highlight(["ZZZ"], "red");
+        let text = dot.to_string();
+        assert_eq!(text.matches(" -> ").count(), 2);
+        assert!(text.contains("    \"ZZZ\";\n"));
+        assert!(text.ends_with("    \"ZZZ\" [style=filled, fillcolor=\"red\"];\n}\n"));
+    }
+}
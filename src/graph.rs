@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// Maps labels like `AAA` to dense ids `0..len` in order of first appearance, so that
+/// per-node data can live in a `Vec` instead of a `HashMap`.
+///
+/// ```
+/// # use advent_of_code::graph::Interner;
+/// let mut names = Interner::new();
+/// assert_eq!(names.intern("AAA"), 0);
+/// assert_eq!(names.intern("BBB"), 1);
+/// assert_eq!(names.intern("AAA"), 0);
+/// assert_eq!(names.label(1), "BBB");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Interner<'a> {
+    ids: HashMap<&'a str, usize>,
+    labels: Vec<&'a str>,
+}
+
+impl<'a> Interner<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the id of `label`, assigning the next free id if it is new.
+    pub fn intern(&mut self, label: &'a str) -> usize {
+        *self.ids.entry(label).or_insert_with(|| {
+            self.labels.push(label);
+            self.labels.len() - 1
+        })
+    }
+
+    /// Returns the id of `label` if it has been interned.
+    pub fn get(&self, label: &str) -> Option<usize> {
+        self.ids.get(label).copied()
+    }
+
+    /// Returns the label of `id`.
+    ///
+    /// # Panics
+    /// Panics if `id` was not handed out by this interner.
+    pub fn label(&self, id: usize) -> &'a str {
+        self.labels[id]
+    }
+
+    /// All labels, indexed by id.
+    pub fn labels(&self) -> &[&'a str] {
+        &self.labels
+    }
+
+    pub fn len(&self) -> usize {
+        self.labels.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.labels.is_empty()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A directed graph over named nodes, with the neighbours of every node stored contiguously
+/// so that a traversal step is plain array indexing.
+///
+/// Neighbours keep the order they were listed in, which matters for puzzles like
+/// `AAA = (BBB, CCC)` where the first neighbour is "left" and the second "right".
+///
+/// # Display
+/// The graph displays as one `label -> neighbour, neighbour` line per node, for debugging.
+///
+/// ```
+/// # use advent_of_code::graph::Graph;
+/// let graph = Graph::from_adjacency([("AAA", ["BBB", "CCC"]), ("BBB", ["AAA", "ZZZ"])]);
+/// let aaa = graph.id("AAA").unwrap();
+/// let right = graph.neighbors(aaa)[1];
+/// assert_eq!(graph.label(right), "CCC");
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct Graph<'a> {
+    names: Interner<'a>,
+    /// Neighbours of node `i` are `targets[offsets[i]..offsets[i + 1]]`.
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+}
+
+impl<'a> Graph<'a> {
+    /// Builds a graph from a list of nodes, each with its outgoing neighbours in order.
+    /// Nodes that only appear as neighbours are added without outgoing edges.
+    pub fn from_adjacency<I, N>(lists: I) -> Self
+    where
+        I: IntoIterator<Item = (&'a str, N)>,
+        N: IntoIterator<Item = &'a str>,
+    {
+        let mut names = Interner::new();
+        let mut edges = vec![];
+        for (source, neighbors) in lists {
+            let source = names.intern(source);
+            for target in neighbors {
+                edges.push((source, names.intern(target)));
+            }
+        }
+        Self::build(names, edges)
+    }
+
+    /// Builds a graph from a list of directed edges.
+    pub fn from_edges(edges: impl IntoIterator<Item = (&'a str, &'a str)>) -> Self {
+        let mut names = Interner::new();
+        let edges = edges
+            .into_iter()
+            .map(|(source, target)| (names.intern(source), names.intern(target)))
+            .collect();
+        Self::build(names, edges)
+    }
+
+    /// Lays out the edges by source with a counting sort, keeping their relative order.
+    fn build(names: Interner<'a>, edges: Vec<(usize, usize)>) -> Self {
+        let mut offsets = vec![0; names.len() + 1];
+        for (source, _) in &edges {
+            offsets[source + 1] += 1;
+        }
+        for i in 1..offsets.len() {
+            offsets[i] += offsets[i - 1];
+        }
+
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        for (source, target) in edges {
+            targets[next[source]] = target;
+            next[source] += 1;
+        }
+
+        Self {
+            names,
+            offsets,
+            targets,
+        }
+    }
+
+    /// The number of nodes.
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// All node ids.
+    pub fn nodes(&self) -> Range<usize> {
+        0..self.len()
+    }
+
+    /// The id of the node called `label`.
+    pub fn id(&self, label: &str) -> Option<usize> {
+        self.names.get(label)
+    }
+
+    /// The label of node `id`.
+    pub fn label(&self, id: usize) -> &'a str {
+        self.names.label(id)
+    }
+
+    pub fn names(&self) -> &Interner<'a> {
+        &self.names
+    }
+
+    /// The outgoing neighbours of node `id` in the order they were added.
+    pub fn neighbors(&self, id: usize) -> &[usize] {
+        &self.targets[self.offsets[id]..self.offsets[id + 1]]
+    }
+
+    /// Iterates every edge as `(source, target)`.
+    pub fn edges(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.nodes()
+            .flat_map(move |id| self.neighbors(id).iter().map(move |t| (id, *t)))
+    }
+}
+
+impl Display for Graph<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for id in self.nodes() {
+            let neighbors: Vec<_> = self.neighbors(id).iter().map(|n| self.label(*n)).collect();
+            writeln!(f, "{} -> {}", self.label(id), neighbors.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Graph, Interner};
+
+    #[test]
+    fn interns_in_order_of_appearance() {
+        let mut names = Interner::new();
+        let ids: Vec<_> = ["b", "a", "b", "c", "a"]
+            .into_iter()
+            .map(|l| names.intern(l))
+            .collect();
+        assert_eq!(ids, vec![0, 1, 0, 2, 1]);
+        assert_eq!(names.labels(), &["b", "a", "c"]);
+        assert_eq!(names.get("c"), Some(2));
+        assert_eq!(names.get("d"), None);
+    }
+
+    #[test]
+    fn adjacency_keeps_neighbour_order() {
+        let graph = Graph::from_adjacency([
+            ("AAA", ["BBB", "CCC"]),
+            ("BBB", ["DDD", "EEE"]),
+            ("CCC", ["ZZZ", "GGG"]),
+        ]);
+        assert_eq!(graph.len(), 7);
+        let ccc = graph.id("CCC").unwrap();
+        let labels: Vec<_> = graph
+            .neighbors(ccc)
+            .iter()
+            .map(|n| graph.label(*n))
+            .collect();
+        assert_eq!(labels, vec!["ZZZ", "GGG"]);
+        assert!(graph.neighbors(graph.id("ZZZ").unwrap()).is_empty());
+        assert_eq!(graph.edges().count(), 6);
+    }
+
+    #[test]
+    fn builds_from_unordered_edges() {
+        let graph = Graph::from_edges([("a", "b"), ("c", "a"), ("a", "c"), ("b", "c")]);
+        let a = graph.id("a").unwrap();
+        assert_eq!(graph.neighbors(a), &[1, 2]);
+        assert_eq!(graph.to_string(), "a -> b, c\nb -> c\nc -> a\n");
+    }
+}
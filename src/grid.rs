@@ -0,0 +1,359 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::{Index, IndexMut};
+use std::str::FromStr;
+
+/// A position in a [`Grid`] as `(x, y)`, where `(0, 0)` is the top-left cell.
+pub type Pos = (usize, usize);
+
+/// Offsets of the four orthogonal neighbours as `(dx, dy)`, clockwise starting north.
+pub const DIRECTIONS_4: [(isize, isize); 4] = [(0, -1), (1, 0), (0, 1), (-1, 0)];
+
+/// Offsets of all eight neighbours as `(dx, dy)`, in reading order.
+pub const DIRECTIONS_8: [(isize, isize); 8] = [
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+
+/// A dense, rectangular two-dimensional grid stored in row-major order.
+///
+/// # Display
+/// A grid of displayable cells displays as one line of text per row.
+///
+/// ```
+/// # use advent_of_code::grid::Grid;
+/// let grid: Grid<char> = "ab\ncd".parse().unwrap();
+/// assert_eq!(grid[(1, 0)], 'b');
+/// assert_eq!(grid.to_string(), "ab\ncd\n");
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid of the given size with every cell set to `fill`.
+    pub fn new(width: usize, height: usize, fill: T) -> Self
+    where
+        T: Clone,
+    {
+        Self {
+            width,
+            height,
+            cells: vec![fill; width * height],
+        }
+    }
+
+    /// Parses a grid from text with one row per line, converting every character with `f`.
+    /// Returns an error if the lines differ in length.
+    pub fn parse_with(s: &str, mut f: impl FnMut(char) -> T) -> Result<Self, GridError> {
+        let mut width = None;
+        let mut height = 0;
+        let mut cells = vec![];
+
+        for (y, line) in s.lines().enumerate() {
+            let len_before = cells.len();
+            cells.extend(line.chars().map(&mut f));
+            let row_width = cells.len() - len_before;
+
+            match width {
+                None => width = Some(row_width),
+                Some(width) if width != row_width => {
+                    return Err(GridError {
+                        line: y + 1,
+                        expected: width,
+                        found: row_width,
+                    });
+                }
+                Some(_) => {}
+            }
+            height += 1;
+        }
+
+        Ok(Self {
+            width: width.unwrap_or(0),
+            height,
+            cells,
+        })
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if `(x, y)` lies inside the grid.
+    pub fn contains(&self, (x, y): Pos) -> bool {
+        x < self.width && y < self.height
+    }
+
+    /// Returns a reference to the cell at `pos`, or [`None`] if it is out of bounds.
+    pub fn get(&self, pos: Pos) -> Option<&T> {
+        self.contains(pos)
+            .then(|| &self.cells[pos.1 * self.width + pos.0])
+    }
+
+    /// Returns a mutable reference to the cell at `pos`, or [`None`] if it is out of bounds.
+    pub fn get_mut(&mut self, pos: Pos) -> Option<&mut T> {
+        if self.contains(pos) {
+            Some(&mut self.cells[pos.1 * self.width + pos.0])
+        } else {
+            None
+        }
+    }
+
+    /// Moves `pos` by `(dx, dy)`, returning [`None`] if the result leaves the grid.
+    pub fn offset(&self, (x, y): Pos, (dx, dy): (isize, isize)) -> Option<Pos> {
+        let pos = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+        self.contains(pos).then_some(pos)
+    }
+
+    /// Iterates the in-bounds orthogonal neighbours of `pos`, see [`DIRECTIONS_4`].
+    pub fn neighbors4(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_4
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Iterates the in-bounds neighbours of `pos` including diagonals, see [`DIRECTIONS_8`].
+    pub fn neighbors8(&self, pos: Pos) -> impl Iterator<Item = Pos> + '_ {
+        DIRECTIONS_8
+            .into_iter()
+            .filter_map(move |d| self.offset(pos, d))
+    }
+
+    /// Returns row `y` as a slice.
+    ///
+    /// # Panics
+    /// Panics if `y` is out of bounds.
+    pub fn row(&self, y: usize) -> &[T] {
+        assert!(y < self.height, "row {y} out of bounds");
+        &self.cells[y * self.width..(y + 1) * self.width]
+    }
+
+    /// Iterates the rows from top to bottom.
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        // `chunks` panics on a zero chunk size, which an empty grid would have.
+        self.cells.chunks(self.width.max(1)).take(self.height)
+    }
+
+    /// Iterates the cells of column `x` from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if `x` is out of bounds.
+    pub fn column(&self, x: usize) -> impl Iterator<Item = &T> {
+        assert!(x < self.width, "column {x} out of bounds");
+        self.cells.iter().skip(x).step_by(self.width)
+    }
+
+    /// Iterates the columns from left to right.
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T>> {
+        (0..self.width).map(|x| self.column(x))
+    }
+
+    /// Iterates every position in reading order.
+    pub fn positions(&self) -> impl Iterator<Item = Pos> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// Iterates every cell along with its position, in reading order.
+    pub fn iter(&self) -> impl Iterator<Item = (Pos, &T)> {
+        self.positions().zip(self.cells.iter())
+    }
+
+    /// Iterates the positions of all cells matching `pred`, in reading order.
+    pub fn find_all<'a>(
+        &'a self,
+        pred: impl Fn(&T) -> bool + 'a,
+    ) -> impl Iterator<Item = Pos> + 'a {
+        self.iter()
+            .filter(move |(_, cell)| pred(cell))
+            .map(|(pos, _)| pos)
+    }
+
+    /// Returns the position of the first cell matching `pred`, in reading order.
+    pub fn find(&self, pred: impl Fn(&T) -> bool) -> Option<Pos> {
+        self.find_all(pred).next()
+    }
+
+    /// Converts every cell with `f`, keeping the shape of the grid.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid {
+            width: self.width,
+            height: self.height,
+            cells: self.cells.iter().map(f).collect(),
+        }
+    }
+}
+
+impl<T> Index<Pos> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, pos: Pos) -> &Self::Output {
+        self.get(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds"))
+    }
+}
+
+impl<T> IndexMut<Pos> for Grid<T> {
+    fn index_mut(&mut self, pos: Pos) -> &mut Self::Output {
+        self.get_mut(pos)
+            .unwrap_or_else(|| panic!("position {pos:?} out of bounds"))
+    }
+}
+
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Grid<char> {
+    type Err = GridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse_with(s, |c| c)
+    }
+}
+
+/// An error which can be returned when parsing a [`Grid`] from lines of unequal length.
+#[derive(Debug, PartialEq, Eq)]
+pub struct GridError {
+    /// The 1-based line number of the first line that does not match the first line's length.
+    pub line: usize,
+    pub expected: usize,
+    pub found: usize,
+}
+
+impl Error for GridError {}
+
+impl Display for GridError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "line {} has {} cells, expecting {} like the first line",
+            self.line, self.found, self.expected
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Grid, GridError};
+
+    fn get_grid() -> Grid<char> {
+        "123\n456\n789\n0ab".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_and_displays() {
+        let grid = get_grid();
+        assert_eq!(grid.width(), 3);
+        assert_eq!(grid.height(), 4);
+        assert_eq!(grid[(2, 1)], '6');
+        assert_eq!(grid.to_string(), "123\n456\n789\n0ab\n");
+    }
+
+    #[test]
+    fn rejects_ragged_rows() {
+        let err = "123\n45\n789".parse::<Grid<char>>().unwrap_err();
+        assert_eq!(
+            err,
+            GridError {
+                line: 2,
+                expected: 3,
+                found: 2
+            }
+        );
+    }
+
+    #[test]
+    fn parses_empty_input() {
+        let grid: Grid<char> = "".parse().unwrap();
+        assert_eq!((grid.width(), grid.height()), (0, 0));
+        assert_eq!(grid.rows().count(), 0);
+        assert_eq!(grid.to_string(), "");
+    }
+
+    #[test]
+    fn bounds_checked_access() {
+        let mut grid = get_grid();
+        assert_eq!(grid.get((3, 0)), None);
+        assert_eq!(grid.get((0, 4)), None);
+        assert_eq!(grid.offset((0, 0), (-1, 0)), None);
+        assert_eq!(grid.offset((1, 1), (1, 1)), Some((2, 2)));
+        *grid.get_mut((0, 0)).unwrap() = 'x';
+        grid[(1, 0)] = 'y';
+        assert_eq!(grid.row(0), &['x', 'y', '3']);
+    }
+
+    #[test]
+    #[should_panic]
+    fn index_out_of_bounds_panics() {
+        let grid = get_grid();
+        let _ = grid[(3, 3)];
+    }
+
+    #[test]
+    fn neighbors() {
+        let grid = get_grid();
+        assert_eq!(
+            grid.neighbors4((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1)]
+        );
+        assert_eq!(grid.neighbors4((1, 1)).count(), 4);
+        assert_eq!(
+            grid.neighbors8((0, 0)).collect::<Vec<_>>(),
+            vec![(1, 0), (0, 1), (1, 1)]
+        );
+        assert_eq!(grid.neighbors8((1, 1)).count(), 8);
+        assert_eq!(grid.neighbors8((2, 3)).count(), 3);
+    }
+
+    #[test]
+    fn rows_and_columns() {
+        let grid = get_grid();
+        let rows: Vec<String> = grid.rows().map(|r| r.iter().collect()).collect();
+        assert_eq!(rows, vec!["123", "456", "789", "0ab"]);
+        let columns: Vec<String> = grid.columns().map(|c| c.collect()).collect();
+        assert_eq!(columns, vec!["1470", "258a", "369b"]);
+    }
+
+    #[test]
+    fn find_all() {
+        let grid = get_grid();
+        let found: Vec<_> = grid.find_all(|c| c.is_ascii_alphabetic()).collect();
+        assert_eq!(found, vec![(1, 3), (2, 3)]);
+        assert_eq!(grid.find(|c| *c == '5'), Some((1, 1)));
+        assert_eq!(grid.find(|c| *c == 'z'), None);
+    }
+
+    #[test]
+    fn maps_cells() {
+        let digits = get_grid().map(|c| c.to_digit(10).unwrap_or(0));
+        assert_eq!(digits[(2, 2)], 9);
+        assert_eq!(digits.to_string(), "123\n456\n789\n000\n");
+    }
+}
@@ -1,4 +1,16 @@
+pub mod bitset;
+pub mod cycles;
 mod day;
+pub mod dot;
+pub mod graph;
+pub mod grid;
+pub mod math;
+pub mod memo;
+pub mod parse;
+pub mod patterns;
+pub mod poly;
+pub mod ranges;
+pub mod search;
 pub mod template;
 
 pub use day::*;
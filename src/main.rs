@@ -1,10 +1,10 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve};
+use advent_of_code::template::commands::{all, download, read, scaffold, scale, solve, stars};
 use args::{parse, AppArguments};
 
 mod args {
     use std::process;
 
-    use advent_of_code::Day;
+    use advent_of_code::{template::export::Format, Day};
 
     pub enum AppArguments {
         Download {
@@ -16,16 +16,26 @@ mod args {
         Scaffold {
             day: Day,
         },
+        Scale {
+            day: Day,
+        },
         Solve {
             day: Day,
             release: bool,
             time: bool,
+            allocs: bool,
+            viz: bool,
+            dot: Option<String>,
             submit: Option<u8>,
         },
         All {
             release: bool,
             time: bool,
+            allocs: bool,
+            format: Option<Format>,
+            output: Option<String>,
         },
+        Stars,
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -35,6 +45,9 @@ mod args {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
                 time: args.contains("--time"),
+                allocs: args.contains("--allocs"),
+                format: args.opt_value_from_str("--format")?,
+                output: args.opt_value_from_str("--output")?,
             },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
@@ -45,12 +58,19 @@ mod args {
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
             },
+            Some("scale") => AppArguments::Scale {
+                day: args.free_from_str()?,
+            },
             Some("solve") => AppArguments::Solve {
                 day: args.free_from_str()?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 time: args.contains("--time"),
+                allocs: args.contains("--allocs"),
+                viz: args.contains("--viz"),
+                dot: args.opt_value_from_str("--dot")?,
             },
+            Some("stars") => AppArguments::Stars,
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -77,16 +97,27 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release, time } => all::handle(release, time),
+            AppArguments::All {
+                release,
+                time,
+                allocs,
+                format,
+                output,
+            } => all::handle(release, time, allocs, format, output),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
             AppArguments::Scaffold { day } => scaffold::handle(day),
+            AppArguments::Scale { day } => scale::handle(day),
             AppArguments::Solve {
                 day,
                 release,
                 time,
+                allocs,
+                viz,
+                dot,
                 submit,
-            } => solve::handle(day, release, time, submit),
+            } => solve::handle(day, release, time, allocs, viz, dot, submit),
+            AppArguments::Stars => stars::handle(),
         },
     };
 }
@@ -0,0 +1,353 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+
+/// Greatest common divisor, `gcd(0, 0) == 0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Least common multiple, or [`None`] if it does not fit into a `u64`. `lcm(x, 0) == 0`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Greatest common divisor of all values, `0` for an empty iterator.
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// Least common multiple of all values, `1` for an empty iterator,
+/// or [`None`] if it does not fit into a `u64`.
+///
+/// ```
+/// # use advent_of_code::math::lcm_all;
+/// assert_eq!(lcm_all([4, 6, 10]), Some(60));
+/// assert_eq!(lcm_all([u64::MAX, 2]), None);
+/// ```
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Extended Euclidean algorithm. Returns `(g, x, y)` with `a·x + b·y = g = gcd(a, b)`
+/// and `g >= 0`.
+///
+/// Returns [`None`] on overflow, which needs `i128::MIN` as one of the inputs.
+pub fn extended_gcd(a: i128, b: i128) -> Option<(i128, i128, i128)> {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1i128, 0i128);
+    let (mut old_y, mut y) = (0i128, 1i128);
+
+    while r != 0 {
+        let q = old_r.checked_div(r)?;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x.checked_sub(q.checked_mul(x)?)?);
+        (old_y, y) = (y, old_y.checked_sub(q.checked_mul(y)?)?);
+    }
+
+    if old_r < 0 {
+        Some((old_r.checked_neg()?, -old_x, -old_y))
+    } else {
+        Some((old_r, old_x, old_y))
+    }
+}
+
+/// The inverse of `a` modulo `m`, or [`None`] if `a` and `m` are not coprime.
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(a.into(), m.into())?;
+    if g != 1 {
+        return None;
+    }
+    // `x` lies within `-m..=m` so the result fits into `m`'s type.
+    Some(x.rem_euclid(m.into()) as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned by [`crt`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// Two of the congruences contradict each other, e.g. `x ≡ 1 (mod 4)` and `x ≡ 2 (mod 6)`.
+    NoSolution,
+    /// The combined modulus does not fit into a `u64`.
+    Overflow,
+}
+
+impl Error for CrtError {}
+
+impl Display for CrtError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CrtError::NoSolution => write!(f, "the congruences have no common solution"),
+            CrtError::Overflow => write!(f, "the combined modulus overflows a u64"),
+        }
+    }
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese Remainder
+/// Theorem. The moduli do not need to be pairwise coprime.
+///
+/// Returns `(x, m)` where `x` is the smallest non-negative solution and `m` the lcm of all
+/// moduli, so that every solution is `x + k·m`. An empty system yields `(0, 1)`.
+///
+/// # Panics
+/// Panics if a modulus is zero.
+///
+/// ```
+/// # use advent_of_code::math::{crt, CrtError};
+/// assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+/// assert_eq!(crt([(3, 4), (5, 6)]), Ok((11, 12)));
+/// assert_eq!(crt([(1, 4), (2, 6)]), Err(CrtError::NoSolution));
+/// ```
+pub fn crt(congruences: impl IntoIterator<Item = (u64, u64)>) -> Result<(u64, u64), CrtError> {
+    congruences
+        .into_iter()
+        .try_fold((0, 1), |(x, m), (residue, modulus)| {
+            assert!(modulus != 0, "modulus must not be zero");
+            merge((x, m), (residue % modulus, modulus))
+        })
+}
+
+/// Merges `x ≡ a1 (mod m1)` and `x ≡ a2 (mod m2)` into a single congruence.
+fn merge((a1, m1): (u64, u64), (a2, m2): (u64, u64)) -> Result<(u64, u64), CrtError> {
+    let g = gcd(m1, m2);
+    let (a1, m1, a2, m2, g) = (a1 as u128, m1 as u128, a2 as u128, m2 as u128, g as u128);
+
+    let diff = (a2 + m2 - a1 % m2) % m2;
+    if diff % g != 0 {
+        return Err(CrtError::NoSolution);
+    }
+
+    let lcm = m1 / g * m2;
+    if lcm > u64::MAX.into() {
+        return Err(CrtError::Overflow);
+    }
+
+    // solve `m1·k ≡ diff (mod m2)` for `k`, reduced by the common divisor.
+    let step = m2 / g;
+    let inverse = mod_inverse((m1 / g % step) as u64, step as u64).unwrap_or(0) as u128;
+    let k = diff / g % step * inverse % step;
+
+    // `m1·k < m1·(m2 / g) = lcm`, so none of these overflow a u128.
+    Ok((((a1 + m1 * k) % lcm) as u64, lcm as u64))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// The exact integer square root, i.e. the largest `r` with `r² <= n`.
+///
+/// ```
+/// # use advent_of_code::math::isqrt;
+/// assert_eq!(isqrt(24), 4);
+/// assert_eq!(isqrt(25), 5);
+/// assert_eq!(isqrt(u128::MAX), u64::MAX.into());
+/// ```
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+
+    // newton's method from a power of two above the root decreases monotonically.
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let next = (x + n / x) / 2;
+        if next >= x {
+            return x;
+        }
+        x = next;
+    }
+}
+
+/// The integers strictly between the two real roots of `a·x² + b·x + c`, i.e. all `x`
+/// where the quadratic has the opposite sign of `a`. The range is empty if there are no
+/// integers between the roots or no real roots at all.
+///
+/// The bounds are computed exactly with integer arithmetic, so tied boundaries (where the
+/// quadratic is exactly zero) are excluded. The discriminant is kept unsigned, so this never
+/// overflows while `a`, `b` and `c` fit into a `u64` and `c` does not have the opposite sign
+/// of `a`, which covers e.g. every race `h·(t - h) > d` with `u64` times and distances.
+/// Returns [`None`] if `a` is zero or an intermediate result overflows.
+///
+/// ```
+/// # use advent_of_code::math::between_roots;
+/// // holding the button for `h` ms in a 7 ms race beats 9 mm when `h·(7 - h) > 9`.
+/// assert_eq!(between_roots(1, -7, 9), Some(2..=5));
+/// // a record of 10 mm in a 7 ms race is tied at `h = 2` and `h = 5`.
+/// assert_eq!(between_roots(1, -7, 10), Some(3..=4));
+/// ```
+pub fn between_roots(a: i128, b: i128, c: i128) -> Option<RangeInclusive<i128>> {
+    if a == 0 {
+        return None;
+    }
+    let (a, b, c) = if a < 0 {
+        (a.checked_neg()?, b.checked_neg()?, c.checked_neg()?)
+    } else {
+        (a, b, c)
+    };
+    let empty = RangeInclusive::new(1, 0);
+
+    // `b²` alone exceeds an i128 for a `u64` sized `b`, but always fits into a u128.
+    let b_squared = b.unsigned_abs().checked_mul(b.unsigned_abs())?;
+    let four_ac = a
+        .unsigned_abs()
+        .checked_mul(c.unsigned_abs())
+        .and_then(|ac| ac.checked_mul(4));
+    let discriminant = if c >= 0 {
+        // `4ac` only lowers the discriminant, if it is too large to represent it is negative.
+        match four_ac {
+            Some(four_ac) if four_ac < b_squared => b_squared - four_ac,
+            _ => return Some(empty),
+        }
+    } else {
+        b_squared.checked_add(four_ac?)?
+    };
+
+    // `x` lies strictly between the roots exactly when `|2a·x + b| < √D`, which avoids
+    // evaluating `a·x²`, as that overflows long before the bounds do.
+    let inside = |x: i128| -> Option<bool> {
+        let v = a.checked_mul(2)?.checked_mul(x)?.checked_add(b)?;
+        Some(
+            v.unsigned_abs()
+                .checked_mul(v.unsigned_abs())
+                .is_some_and(|square| square < discriminant),
+        )
+    };
+
+    let root = i128::try_from(isqrt(discriminant)).ok()?;
+    let denominator = a.checked_mul(2)?;
+
+    // with the truncated root, the real roots lie within half a step outside of these
+    // estimates, so each bound is either the estimate itself or its inner neighbour.
+    let low = (b.checked_neg()?.checked_sub(root)?).div_euclid(denominator);
+    let low = if inside(low)? { low } else { low + 1 };
+    let high = -(b.checked_sub(root)?.div_euclid(denominator));
+    let high = if inside(high)? { high } else { high - 1 };
+
+    if low <= high && inside(low)? {
+        Some(low..=high)
+    } else {
+        Some(empty)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd(0, 0), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm(0, 6), Some(0));
+        assert_eq!(lcm(u64::MAX, u64::MAX - 1), None);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([2, 3, 4, 5]), Some(60));
+    }
+
+    #[test]
+    fn extended_euclid() {
+        for (a, b) in [
+            (240, 46),
+            (-240, 46),
+            (17, 0),
+            (0, -5),
+            (i64::MAX as i128, 3),
+        ] {
+            let (g, x, y) = extended_gcd(a, b).unwrap();
+            assert_eq!(a * x + b * y, g);
+            assert_eq!(
+                g,
+                gcd(a.unsigned_abs() as u64, b.unsigned_abs() as u64) as i128
+            );
+        }
+        assert_eq!(extended_gcd(i128::MIN, 0), None);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(4, 6), None);
+    }
+
+    #[test]
+    fn crt_coprime() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Ok((23, 105)));
+        assert_eq!(crt([(10, 3)]), Ok((1, 3)));
+        assert_eq!(crt([]), Ok((0, 1)));
+    }
+
+    #[test]
+    fn crt_not_coprime() {
+        assert_eq!(crt([(3, 4), (5, 6)]), Ok((11, 12)));
+        assert_eq!(crt([(0, 6), (0, 10), (0, 15)]), Ok((0, 30)));
+        assert_eq!(crt([(1, 4), (2, 6)]), Err(CrtError::NoSolution));
+    }
+
+    #[test]
+    fn crt_overflow() {
+        let big = (1u64 << 63) - 25; // prime
+        assert_eq!(crt([(1, big), (2, 3)]), Err(CrtError::Overflow));
+        let (x, m) = crt([(1, big), (0, 2)]).unwrap();
+        assert_eq!(m, big * 2);
+        assert_eq!((x % big, x % 2), (1, 0));
+    }
+
+    #[test]
+    fn integer_sqrt() {
+        for n in 0..10_000u128 {
+            let r = isqrt(n);
+            assert!(r * r <= n && (r + 1) * (r + 1) > n, "isqrt({n}) = {r}");
+        }
+        assert_eq!(isqrt(u64::MAX as u128), (1 << 32) - 1);
+        assert_eq!(isqrt(u128::MAX), u64::MAX as u128);
+        let big = (u64::MAX - 7) as u128;
+        assert_eq!(isqrt(big * big), big);
+        assert_eq!(isqrt(big * big - 1), big - 1);
+    }
+
+    #[test]
+    fn quadratic_bounds() {
+        assert_eq!(between_roots(1, -7, 9), Some(2..=5));
+        assert_eq!(between_roots(1, -7, 10), Some(3..=4));
+        assert_eq!(between_roots(1, -30, 200), Some(11..=19));
+        assert_eq!(between_roots(-1, 7, -9), Some(2..=5));
+        assert_eq!(between_roots(1, 0, -4), Some(-1..=1));
+        assert!(between_roots(1, 0, 4).unwrap().is_empty());
+        assert!(between_roots(4, -4, 1).unwrap().is_empty());
+        assert!(between_roots(1, -3, 2).unwrap().is_empty());
+        assert_eq!(between_roots(0, 1, 1), None);
+        assert_eq!(between_roots(1, i128::MAX, 0), None);
+
+        // races as long as a u64 allows, where `t²` exceeds an i128.
+        let t = i128::from(u64::MAX);
+        assert_eq!(between_roots(1, -t, 0), Some(1..=t - 1));
+        assert_eq!(between_roots(1, -t, t), Some(2..=t - 2));
+        // the best hold times `(t ± 1) / 2` reach `(t² - 1) / 4`.
+        let (h, best) = ((t - 1) / 2, ((t - 1) / 2) * ((t + 1) / 2));
+        assert_eq!(between_roots(1, -t, best - 1), Some(h..=h + 1));
+        assert!(between_roots(1, -t, best).unwrap().is_empty());
+        assert!(between_roots(1, -t, i128::MAX).unwrap().is_empty());
+    }
+
+    #[test]
+    fn quadratic_bounds_match_brute_force() {
+        for t in 0..40i128 {
+            for d in 0..(t * t / 4 + 2) {
+                let expected = (0..=t).filter(|h| h * (t - h) > d).count();
+                let range = between_roots(1, -t, d).unwrap();
+                assert_eq!(range.count(), expected, "t = {t}, d = {d}");
+            }
+        }
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Cache lookups of every [`Memo`] in the process, so the runner can report them
+/// without the solution having to hand its caches back.
+static HITS: AtomicU64 = AtomicU64::new(0);
+static MISSES: AtomicU64 = AtomicU64::new(0);
+
+/// Cache hits and misses counted across all [`Memo`]s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct MemoStats {
+    pub hits: u64,
+    pub misses: u64,
+}
+
+impl MemoStats {
+    /// The share of lookups answered from a cache, between `0.0` and `1.0`.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            #[allow(clippy::cast_precision_loss)]
+            let rate = self.hits as f64 / lookups as f64;
+            rate
+        }
+    }
+}
+
+/// The lookups counted since the last [`reset_stats`].
+pub fn stats() -> MemoStats {
+    MemoStats {
+        hits: HITS.load(Ordering::Relaxed),
+        misses: MISSES.load(Ordering::Relaxed),
+    }
+}
+
+pub fn reset_stats() {
+    HITS.store(0, Ordering::Relaxed);
+    MISSES.store(0, Ordering::Relaxed);
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A cache for a recursive function keyed by its (hashable) arguments.
+///
+/// The function receives a `recurse` callback to use instead of calling itself, so that
+/// every recursive call goes through the cache as well.
+///
+/// ```
+/// # use advent_of_code::memo::Memo;
+/// let mut memo = Memo::new();
+/// let fib = |recurse: &mut dyn FnMut(u64) -> u64, n: u64| {
+///     if n < 2 { n } else { recurse(n - 1) + recurse(n - 2) }
+/// };
+/// assert_eq!(memo.call(90, &fib), 2_880_067_194_370_816_120);
+/// assert_eq!(memo.misses(), 91);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Memo<K, V> {
+    cache: HashMap<K, V>,
+    hits: u64,
+    misses: u64,
+}
+
+impl<K, V> Default for Memo<K, V> {
+    fn default() -> Self {
+        Self {
+            cache: HashMap::new(),
+            hits: 0,
+            misses: 0,
+        }
+    }
+}
+
+impl<K: Clone + Eq + Hash, V: Clone> Memo<K, V> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns `f(key)`, computing it only if it is not cached yet.
+    pub fn call<F>(&mut self, key: K, f: &F) -> V
+    where
+        F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+    {
+        if let Some(value) = self.cache.get(&key) {
+            self.hits += 1;
+            HITS.fetch_add(1, Ordering::Relaxed);
+            return value.clone();
+        }
+
+        self.misses += 1;
+        MISSES.fetch_add(1, Ordering::Relaxed);
+
+        let value = f(&mut |k| self.call(k, f), key.clone());
+        self.cache.insert(key, value.clone());
+        value
+    }
+
+    /// Returns the cached value for `key` without computing it.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
+    }
+
+    /// The number of cached values.
+    pub fn len(&self) -> usize {
+        self.cache.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cache.is_empty()
+    }
+
+    /// Lookups of this cache that found a value.
+    pub fn hits(&self) -> u64 {
+        self.hits
+    }
+
+    /// Lookups of this cache that had to compute the value.
+    pub fn misses(&self) -> u64 {
+        self.misses
+    }
+
+    /// Drops all cached values, e.g. between independent lines of the input.
+    pub fn clear(&mut self) {
+        self.cache.clear();
+    }
+}
+
+/// Computes `f(key)` with a fresh [`Memo`], see there.
+pub fn memoize<K, V, F>(key: K, f: F) -> V
+where
+    K: Clone + Eq + Hash,
+    V: Clone,
+    F: Fn(&mut dyn FnMut(K) -> V, K) -> V,
+{
+    Memo::new().call(key, &f)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{memoize, Memo};
+
+    /// Counts the ways to split `s` into the given words.
+    fn arrangements(s: &str, words: &[&str]) -> u64 {
+        memoize(0, |recurse, i: usize| {
+            if i == s.len() {
+                return 1;
+            }
+            words
+                .iter()
+                .filter(|w| s[i..].starts_with(*w))
+                .map(|w| recurse(i + w.len()))
+                .sum()
+        })
+    }
+
+    #[test]
+    fn counts_arrangements() {
+        assert_eq!(arrangements("abab", &["a", "b", "ab"]), 4);
+        assert_eq!(arrangements("abc", &["a", "b"]), 0);
+        assert_eq!(
+            arrangements(&"a".repeat(80), &["a", "aa"]),
+            37_889_062_373_143_906
+        );
+    }
+
+    #[test]
+    fn counts_hits_and_misses() {
+        let grid_paths = |recurse: &mut dyn FnMut((u32, u32)) -> u64, (x, y): (u32, u32)| {
+            if x == 0 || y == 0 {
+                1
+            } else {
+                recurse((x - 1, y)) + recurse((x, y - 1))
+            }
+        };
+
+        let mut memo = Memo::new();
+        assert_eq!(memo.call((3, 3), &grid_paths), 20);
+        // every cell of the 4x4 grid except the unreachable corner is computed once.
+        assert_eq!(memo.misses(), 15);
+        assert_eq!(memo.len(), 15);
+        let hits = memo.hits();
+        assert!(hits > 0);
+
+        assert_eq!(memo.call((3, 3), &grid_paths), 20);
+        assert_eq!(memo.hits(), hits + 1);
+        assert_eq!(memo.get(&(1, 1)), Some(&2));
+    }
+}
@@ -0,0 +1,329 @@
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+/// A slice of the puzzle input that remembers where it is located in the full input,
+/// so that parse errors can point at the offending line and column.
+///
+/// ```
+/// # use advent_of_code::parse::Span;
+/// let input = Span::new("Game 1: 3 blue\nGame x: 4 red");
+/// let line = input.lines().nth(1).unwrap();
+/// let (label, _) = line.label().unwrap();
+/// let err = label.strip_prefix("Game ").unwrap().parse::<u32>().unwrap_err();
+/// assert_eq!((err.line, err.column), (2, 6));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Span<'a> {
+    src: &'a str,
+    start: usize,
+    end: usize,
+}
+
+impl<'a> Span<'a> {
+    /// Creates a span covering the whole input.
+    pub fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            start: 0,
+            end: src.len(),
+        }
+    }
+
+    /// The text covered by this span.
+    pub fn as_str(&self) -> &'a str {
+        &self.src[self.start..self.end]
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    /// Creates a span for `s`, which must be a subslice of this span's text.
+    fn sub(&self, s: &'a str) -> Span<'a> {
+        let start = s.as_ptr() as usize - self.src.as_ptr() as usize;
+        debug_assert!(self.start <= start && start + s.len() <= self.end);
+        Span {
+            src: self.src,
+            start,
+            end: start + s.len(),
+        }
+    }
+
+    /// Creates a [`ParseError`] pointing at this span.
+    pub fn error(&self, message: impl Into<String>) -> ParseError {
+        ParseError::new(self.src, self.start, self.end, message.into())
+    }
+
+    pub fn trim(&self) -> Span<'a> {
+        self.sub(self.as_str().trim())
+    }
+
+    /// Iterates the lines of this span.
+    pub fn lines(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.as_str().lines().map(|l| self.sub(l))
+    }
+
+    /// Iterates the blocks of text separated by blank lines, skipping empty ones.
+    pub fn sections(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.as_str()
+            .split("\n\n")
+            .map(|s| self.sub(s).trim())
+            .filter(|s| !s.is_empty())
+    }
+
+    /// Iterates the parts of this span separated by `separator`.
+    pub fn split(&self, separator: &'a str) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.as_str().split(separator).map(|s| self.sub(s))
+    }
+
+    /// Iterates the parts of this span separated by any amount of whitespace.
+    pub fn split_whitespace(&self) -> impl Iterator<Item = Span<'a>> + '_ {
+        self.as_str().split_whitespace().map(|s| self.sub(s))
+    }
+
+    /// Splits the span at the first occurrence of `separator`.
+    pub fn split_once(&self, separator: &str) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        self.as_str()
+            .split_once(separator)
+            .map(|(a, b)| (self.sub(a), self.sub(b)))
+            .ok_or_else(|| self.error(format!("expecting `{separator}`")))
+    }
+
+    /// Removes `prefix` from the start of the span.
+    pub fn strip_prefix(&self, prefix: &str) -> Result<Span<'a>, ParseError> {
+        self.as_str()
+            .strip_prefix(prefix)
+            .map(|s| self.sub(s))
+            .ok_or_else(|| self.prefix_error(prefix))
+    }
+
+    fn prefix_error(&self, prefix: &str) -> ParseError {
+        let len = self
+            .as_str()
+            .char_indices()
+            .nth(prefix.chars().count())
+            .map_or(self.end - self.start, |(i, _)| i);
+        Span {
+            src: self.src,
+            start: self.start,
+            end: self.start + len,
+        }
+        .error(format!("expecting `{prefix}`"))
+    }
+
+    /// Splits a labelled line like `Card 1: 41 48 | 83 86` into its label and trimmed contents.
+    pub fn label(&self) -> Result<(Span<'a>, Span<'a>), ParseError> {
+        let (label, rest) = self.split_once(":")?;
+        Ok((label.trim(), rest.trim()))
+    }
+
+    /// Parses the trimmed span into any [`FromStr`] type.
+    pub fn parse<T: FromStr>(&self) -> Result<T, ParseError> {
+        let trimmed = self.trim();
+        trimmed.as_str().parse().map_err(|_| {
+            let found = trimmed.as_str();
+            if found.is_empty() {
+                trimmed.error(format!("expecting a `{}`", std::any::type_name::<T>()))
+            } else {
+                trimmed.error(format!(
+                    "could not parse `{found}` as `{}`",
+                    std::any::type_name::<T>()
+                ))
+            }
+        })
+    }
+
+    /// Parses a whitespace-separated list of numbers.
+    pub fn numbers<T: FromStr>(&self) -> Result<Vec<T>, ParseError> {
+        self.split_whitespace().map(|s| s.parse()).collect()
+    }
+
+    /// Returns the byte range `start..end` of this span, or an error if the span is too short.
+    pub fn slice(&self, start: usize, end: usize) -> Result<Span<'a>, ParseError> {
+        let s = self.as_str();
+        match s.get(start..end) {
+            Some(field) => Ok(self.sub(field)),
+            None => {
+                let from = if s.is_char_boundary(start) {
+                    start
+                } else {
+                    s.len()
+                };
+                let at = Span {
+                    src: self.src,
+                    start: self.start + from,
+                    end: self.end,
+                };
+                Err(at.error(format!("expecting at least {end} characters")))
+            }
+        }
+    }
+
+    /// Cuts the span into consecutive fixed-width fields, e.g. `[3, 4, 3]` for `AAA = BBB`.
+    pub fn fields(&self, widths: &[usize]) -> Result<Vec<Span<'a>>, ParseError> {
+        let mut start = 0;
+        widths
+            .iter()
+            .map(|width| {
+                let field = self.slice(start, start + width)?;
+                start += width;
+                Ok(field)
+            })
+            .collect()
+    }
+}
+
+impl Display for Span<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error pointing at a location in the puzzle input.
+///
+/// # Display
+/// The error displays as a message followed by the offending line, with carets under the
+/// offending characters.
+///
+/// ```text
+/// error: could not parse `x` as `u32`
+///  --> line 2, column 6
+///   |
+/// 2 | Game x: 4 red
+///   |      ^
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    /// The 1-based line number of the error.
+    pub line: usize,
+    /// The 1-based column of the error, counted in characters.
+    pub column: usize,
+    /// The number of highlighted characters, at least one.
+    pub width: usize,
+    /// The full text of the offending line.
+    pub line_text: String,
+}
+
+impl ParseError {
+    fn new(src: &str, start: usize, end: usize, message: String) -> Self {
+        let line_start = src[..start].rfind('\n').map_or(0, |i| i + 1);
+        let line_end = src[start..].find('\n').map_or(src.len(), |i| start + i);
+        let line_text = src[line_start..line_end].trim_end_matches('\r');
+
+        Self {
+            message,
+            line: src[..start].matches('\n').count() + 1,
+            column: src[line_start..start].chars().count() + 1,
+            width: src[start..end.min(line_end)].chars().count().max(1),
+            line_text: line_text.to_string(),
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+impl Display for ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let gutter = " ".repeat(self.line.to_string().len());
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{gutter}--> line {}, column {}", self.line, self.column)?;
+        writeln!(f, "{gutter} |")?;
+        writeln!(f, "{} | {}", self.line, self.line_text)?;
+        write!(
+            f,
+            "{gutter} | {}{}",
+            " ".repeat(self.column - 1),
+            "^".repeat(self.width)
+        )
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Span;
+
+    #[test]
+    fn splits_sections_and_lines() {
+        let input = Span::new("seeds: 1 2\n\na:\n1 2 3\n4 5 6\n\nb:\n7 8 9\n");
+        let sections: Vec<_> = input.sections().map(|s| s.as_str()).collect();
+        assert_eq!(
+            sections,
+            vec!["seeds: 1 2", "a:\n1 2 3\n4 5 6", "b:\n7 8 9"]
+        );
+        let numbers = input.sections().nth(1).unwrap().lines().nth(2).unwrap();
+        assert_eq!(numbers.numbers::<u32>().unwrap(), vec![4, 5, 6]);
+    }
+
+    #[test]
+    fn labels() {
+        let line = Span::new("Card  1: 41 48 | 83 86");
+        let (label, rest) = line.label().unwrap();
+        assert_eq!(label.as_str(), "Card  1");
+        assert_eq!(
+            label.strip_prefix("Card").unwrap().parse::<u8>().unwrap(),
+            1
+        );
+        let (winners, played) = rest.split_once(" | ").unwrap();
+        assert_eq!(winners.numbers::<u8>().unwrap(), vec![41, 48]);
+        assert_eq!(played.numbers::<u8>().unwrap(), vec![83, 86]);
+    }
+
+    #[test]
+    fn fixed_width_fields() {
+        let line = Span::new("AAA = (BBB, CCC)");
+        let fields: Vec<_> = line
+            .fields(&[3, 4, 3, 2, 3])
+            .unwrap()
+            .iter()
+            .map(|f| f.as_str())
+            .collect();
+        assert_eq!(fields, vec!["AAA", " = (", "BBB", ", ", "CCC"]);
+
+        let err = line.fields(&[3, 20]).unwrap_err();
+        assert_eq!((err.line, err.column), (1, 4));
+    }
+
+    #[test]
+    fn reports_line_and_column() {
+        let input = Span::new("1 2 3\n4 five 6\n");
+        let err = input
+            .lines()
+            .map(|l| l.numbers::<u32>())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap_err();
+        assert_eq!(err.line, 2);
+        assert_eq!(err.column, 3);
+        assert_eq!(err.width, 4);
+        assert_eq!(err.line_text, "4 five 6");
+        assert_eq!(err.message, "could not parse `five` as `u32`");
+    }
+
+    #[test]
+    fn reports_missing_prefix() {
+        let err = Span::new("seeds 1 2").strip_prefix("seeds: ").unwrap_err();
+        assert_eq!(err.message, "expecting `seeds: `");
+        assert_eq!((err.column, err.width), (1, 7));
+    }
+
+    #[test]
+    fn renders_snippet() {
+        let input = Span::new("Game 1: 3 blue\nGame x: 4 red");
+        let line = input.lines().nth(1).unwrap();
+        let err = line.fields(&[5, 1]).unwrap()[1].parse::<u32>().unwrap_err();
+        let expected = [
+            "error: could not parse `x` as `u32`",
+            " --> line 2, column 6",
+            "  |",
+            "2 | Game x: 4 red",
+            "  |      ^",
+        ]
+        .join("\n");
+        assert_eq!(err.to_string(), expected);
+    }
+}
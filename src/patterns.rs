@@ -0,0 +1,254 @@
+use std::cmp::Reverse;
+use std::collections::VecDeque;
+
+/// A match of one of the [`Patterns`] in a haystack, as a byte range `start..end`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Match {
+    /// The index of the matched pattern, in the order the patterns were given.
+    pub pattern: usize,
+    pub start: usize,
+    pub end: usize,
+}
+
+/// An Aho–Corasick automaton: a trie of the patterns where every state also knows where to
+/// continue on a mismatch, flattened into a full transition table over bytes.
+#[derive(Debug, Clone)]
+struct Automaton {
+    /// The state after reading byte `b` in state `s` is `next[s * 256 + b]`.
+    next: Vec<usize>,
+    /// The patterns ending in each state, longest first.
+    outputs: Vec<Vec<usize>>,
+}
+
+impl Automaton {
+    fn build<'p>(patterns: impl IntoIterator<Item = &'p [u8]>) -> Self {
+        // the trie, with 0 as "no child yet", which is safe since the root is no child.
+        let mut next = vec![0; 256];
+        let mut outputs = vec![vec![]];
+        for (id, pattern) in patterns.into_iter().enumerate() {
+            let mut state = 0;
+            for b in pattern {
+                let i = state * 256 + usize::from(*b);
+                if next[i] == 0 {
+                    next[i] = outputs.len();
+                    next.extend([0; 256]);
+                    outputs.push(vec![]);
+                }
+                state = next[i];
+            }
+            outputs[state].push(id);
+        }
+
+        // fill in the mismatch transitions breadth-first, so that the fallback state of every
+        // state (its longest proper suffix in the trie) is complete before it is needed.
+        let mut fallback = vec![0; outputs.len()];
+        let mut queue: VecDeque<usize> = next[..256].iter().copied().filter(|s| *s != 0).collect();
+        while let Some(state) = queue.pop_front() {
+            let inherited = outputs[fallback[state]].clone();
+            outputs[state].extend(inherited);
+            for b in 0..256 {
+                let i = state * 256 + b;
+                let fallback_next = next[fallback[state] * 256 + b];
+                if next[i] == 0 {
+                    next[i] = fallback_next;
+                } else {
+                    fallback[next[i]] = fallback_next;
+                    queue.push_back(next[i]);
+                }
+            }
+        }
+
+        Self { next, outputs }
+    }
+
+    /// Iterates all matches ending in `bytes`, in scanning order.
+    fn scan<'a>(
+        &'a self,
+        lens: &'a [usize],
+        bytes: impl Iterator<Item = u8> + 'a,
+    ) -> impl Iterator<Item = Match> + 'a {
+        let mut state = 0;
+        bytes.enumerate().flat_map(move |(i, b)| {
+            state = self.next[state * 256 + usize::from(b)];
+            self.outputs[state].iter().map(move |p| Match {
+                pattern: *p,
+                start: i + 1 - lens[*p],
+                end: i + 1,
+            })
+        })
+    }
+
+    /// The match that starts first in `bytes`, the longest one on a tie.
+    fn leftmost(&self, lens: &[usize], bytes: impl Iterator<Item = u8>) -> Option<Match> {
+        let longest = lens.iter().copied().max().unwrap_or(0);
+        let mut best: Option<Match> = None;
+        for m in self.scan(lens, bytes) {
+            if let Some(b) = best {
+                if m.end > b.start + longest {
+                    // every match from here on starts after the best one.
+                    break;
+                }
+            }
+            let key = |m: &Match| (m.start, Reverse(m.end));
+            if best.is_none_or(|b| key(&m) < key(&b)) {
+                best = Some(m);
+            }
+        }
+        best
+    }
+}
+
+/// A set of byte string patterns that are searched for all at once, in a single pass over
+/// the haystack regardless of how many patterns there are.
+///
+/// Offsets are in bytes, so patterns and haystacks may be `str`s or raw bytes.
+///
+/// ```
+/// # use advent_of_code::patterns::Patterns;
+/// let words = Patterns::new(["one", "two", "eight"]);
+/// let found: Vec<_> = words.find_overlapping("eightwone").map(|m| m.pattern).collect();
+/// assert_eq!(found, vec![2, 1, 0]);
+/// assert_eq!(words.find_first("eightwone").unwrap().pattern, 2);
+/// assert_eq!(words.find_last("eightwone").unwrap().pattern, 0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Patterns {
+    lens: Vec<usize>,
+    forward: Automaton,
+    /// The automaton of the reversed patterns, for scanning from the end.
+    backward: Automaton,
+}
+
+impl Patterns {
+    /// Builds the matcher for `patterns`.
+    ///
+    /// # Panics
+    /// Panics if a pattern is empty, since it would match everywhere.
+    pub fn new<P: AsRef<[u8]>>(patterns: impl IntoIterator<Item = P>) -> Self {
+        let patterns: Vec<Vec<u8>> = patterns.into_iter().map(|p| p.as_ref().to_vec()).collect();
+        assert!(
+            patterns.iter().all(|p| !p.is_empty()),
+            "patterns must not be empty"
+        );
+        let reversed: Vec<Vec<u8>> = patterns
+            .iter()
+            .map(|p| p.iter().rev().copied().collect())
+            .collect();
+
+        Self {
+            lens: patterns.iter().map(Vec::len).collect(),
+            forward: Automaton::build(patterns.iter().map(Vec::as_slice)),
+            backward: Automaton::build(reversed.iter().map(Vec::as_slice)),
+        }
+    }
+
+    /// The number of patterns.
+    pub fn len(&self) -> usize {
+        self.lens.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lens.is_empty()
+    }
+
+    /// Iterates every occurrence of every pattern, including overlapping ones, ordered by
+    /// where they end. Occurrences ending at the same byte come longest first.
+    pub fn find_overlapping<'a>(
+        &'a self,
+        haystack: &'a (impl AsRef<[u8]> + ?Sized),
+    ) -> impl Iterator<Item = Match> + 'a {
+        self.forward
+            .scan(&self.lens, haystack.as_ref().iter().copied())
+    }
+
+    /// The occurrence that starts first, the longest one on a tie.
+    pub fn find_first(&self, haystack: &(impl AsRef<[u8]> + ?Sized)) -> Option<Match> {
+        self.forward
+            .leftmost(&self.lens, haystack.as_ref().iter().copied())
+    }
+
+    /// The occurrence that ends last, the longest one on a tie. Scans from the end of the
+    /// haystack, so it usually stops after a few bytes.
+    pub fn find_last(&self, haystack: &(impl AsRef<[u8]> + ?Sized)) -> Option<Match> {
+        let bytes = haystack.as_ref();
+        let m = self
+            .backward
+            .leftmost(&self.lens, bytes.iter().rev().copied())?;
+        Some(Match {
+            pattern: m.pattern,
+            start: bytes.len() - m.end,
+            end: bytes.len() - m.start,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Match, Patterns};
+
+    fn spans(patterns: &Patterns, haystack: &str) -> Vec<(usize, usize, usize)> {
+        patterns
+            .find_overlapping(haystack)
+            .map(|m| (m.pattern, m.start, m.end))
+            .collect()
+    }
+
+    #[test]
+    fn finds_overlapping_matches() {
+        let patterns = Patterns::new(["he", "she", "his", "hers"]);
+        assert_eq!(
+            spans(&patterns, "ushers"),
+            vec![(1, 1, 4), (0, 2, 4), (3, 2, 6)]
+        );
+        assert_eq!(spans(&patterns, "ahishers").len(), 4);
+        assert!(spans(&patterns, "xyz").is_empty());
+
+        let repeated = Patterns::new(["aa"]);
+        assert_eq!(spans(&repeated, "aaaa").len(), 3);
+    }
+
+    #[test]
+    fn finds_first_and_last() {
+        let patterns = Patterns::new(["bc", "abcd", "d", "cde"]);
+        let first = patterns.find_first("xabcde").unwrap();
+        assert_eq!(
+            first,
+            Match {
+                pattern: 1,
+                start: 1,
+                end: 5
+            }
+        );
+        let last = patterns.find_last("xabcde").unwrap();
+        assert_eq!((last.pattern, last.start, last.end), (3, 3, 6));
+        assert_eq!(patterns.find_first("xyz"), None);
+        assert_eq!(patterns.find_last(""), None);
+    }
+
+    #[test]
+    fn brute_force_agrees() {
+        let words = ["ab", "b", "bab", "aab", "ba"];
+        let patterns = Patterns::new(words);
+        let haystack = "abaabbababaab";
+        let mut expected = vec![];
+        for end in 1..=haystack.len() {
+            for (id, w) in words.iter().enumerate() {
+                if haystack[..end].ends_with(w) {
+                    expected.push((id, end - w.len(), end));
+                }
+            }
+        }
+        let mut found = spans(&patterns, haystack);
+        found.sort_by_key(|(id, start, end)| (*end, *id, *start));
+        expected.sort_by_key(|(id, start, end)| (*end, *id, *start));
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    #[should_panic(expected = "patterns must not be empty")]
+    fn rejects_empty_patterns() {
+        Patterns::new(["a", ""]);
+    }
+}
@@ -0,0 +1,358 @@
+use std::error::Error;
+use std::fmt::Display;
+
+/// An exact fraction in lowest terms with a positive denominator.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rational {
+    numer: i128,
+    denom: i128,
+}
+
+impl Rational {
+    pub const ZERO: Rational = Rational { numer: 0, denom: 1 };
+
+    /// Creates `numer / denom` in lowest terms, or [`None`] if `denom` is zero or the
+    /// normalized fraction overflows.
+    pub fn new(numer: i128, denom: i128) -> Option<Self> {
+        if denom == 0 {
+            return None;
+        }
+        let mut gcd = numer.unsigned_abs();
+        let mut b = denom.unsigned_abs();
+        while b != 0 {
+            (gcd, b) = (b, gcd % b);
+        }
+        let gcd = i128::try_from(gcd).ok()?;
+        let (numer, denom) = (numer / gcd, denom / gcd);
+        if denom < 0 {
+            Some(Self {
+                numer: numer.checked_neg()?,
+                denom: denom.checked_neg()?,
+            })
+        } else {
+            Some(Self { numer, denom })
+        }
+    }
+
+    pub fn numer(&self) -> i128 {
+        self.numer
+    }
+
+    pub fn denom(&self) -> i128 {
+        self.denom
+    }
+
+    /// The value as an integer, if it is one.
+    pub fn to_integer(&self) -> Option<i128> {
+        (self.denom == 1).then_some(self.numer)
+    }
+
+    pub fn checked_add(&self, other: &Self) -> Option<Self> {
+        Self::new(
+            self.numer
+                .checked_mul(other.denom)?
+                .checked_add(other.numer.checked_mul(self.denom)?)?,
+            self.denom.checked_mul(other.denom)?,
+        )
+    }
+}
+
+impl From<i128> for Rational {
+    fn from(value: i128) -> Self {
+        Self {
+            numer: value,
+            denom: 1,
+        }
+    }
+}
+
+impl Display for Rational {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.denom == 1 {
+            write!(f, "{}", self.numer)
+        } else {
+            write!(f, "{}/{}", self.numer, self.denom)
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when fitting a [`Poly`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PolyError {
+    /// There are no samples to fit.
+    Empty,
+    /// The differences of the samples never vanish, so no polynomial of a degree lower
+    /// than the number of samples fits them, e.g. for `1 2 4 8 16`.
+    NotPolynomial,
+    /// The differences overflow an `i128`.
+    Overflow,
+}
+
+impl Error for PolyError {}
+
+impl Display for PolyError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PolyError::Empty => write!(f, "cannot fit a polynomial to zero samples"),
+            PolyError::NotPolynomial => write!(
+                f,
+                "the samples do not follow a polynomial of a degree lower than their count"
+            ),
+            PolyError::Overflow => write!(f, "the differences of the samples overflow an i128"),
+        }
+    }
+}
+
+/// A polynomial fitted exactly to integer samples taken at the indices `0, 1, 2, ...`.
+///
+/// Stored in Newton form, `p(n) = Σ Δᵏ·C(n, k)`, where `Δᵏ` is the first entry of the k-th
+/// row of the difference table. Binomials of integers are integers, so evaluating at any
+/// index, however far off or negative, never leaves integer arithmetic.
+///
+/// ```
+/// # use advent_of_code::poly::Poly;
+/// let poly = Poly::fit(&[10, 13, 16, 21, 30, 45]).unwrap();
+/// assert_eq!(poly.degree(), 3);
+/// assert_eq!(poly.eval(6), Some(68));
+/// assert_eq!(poly.eval(-1), Some(5));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Poly {
+    /// The leading differences `Δ⁰, Δ¹, ..., Δᵈ`.
+    newton: Vec<i128>,
+}
+
+impl Poly {
+    /// Fits the polynomial of lowest degree through `samples`.
+    ///
+    /// A fit only counts if it is confirmed by at least one sample beyond the `degree + 1`
+    /// that determine it, i.e. a row of the difference table must become all zeros.
+    pub fn fit(samples: &[i64]) -> Result<Self, PolyError> {
+        if samples.is_empty() {
+            return Err(PolyError::Empty);
+        }
+
+        let mut row: Vec<i128> = samples.iter().map(|&s| s.into()).collect();
+        let mut newton = vec![];
+
+        while !row.is_empty() {
+            if row.iter().all(|d| *d == 0) {
+                // an all-zero first row is the zero polynomial, which has no terms.
+                return Ok(Self { newton });
+            }
+            newton.push(row[0]);
+            // reuse the row in place, each entry becomes the difference to its successor.
+            for i in 0..row.len() - 1 {
+                row[i] = row[i + 1].checked_sub(row[i]).ok_or(PolyError::Overflow)?;
+            }
+            row.pop();
+        }
+
+        Err(PolyError::NotPolynomial)
+    }
+
+    /// Interpolates `samples` like [`Poly::fit`], but without asking for a confirming sample:
+    /// the first constant row of the difference table, at the latest the single entry of the
+    /// last row, is taken to stay constant.
+    ///
+    /// Never returns [`PolyError::NotPolynomial`], so `n` samples always give a polynomial of
+    /// degree below `n`.
+    pub fn interpolate(samples: &[i64]) -> Result<Self, PolyError> {
+        if samples.is_empty() {
+            return Err(PolyError::Empty);
+        }
+
+        let mut row: Vec<i128> = samples.iter().map(|&s| s.into()).collect();
+        let mut newton = vec![];
+
+        loop {
+            if row.iter().all(|d| *d == 0) {
+                return Ok(Self { newton });
+            }
+            newton.push(row[0]);
+            if row.iter().all(|d| *d == row[0]) {
+                return Ok(Self { newton });
+            }
+            for i in 0..row.len() - 1 {
+                row[i] = row[i + 1].checked_sub(row[i]).ok_or(PolyError::Overflow)?;
+            }
+            row.pop();
+        }
+    }
+
+    /// The degree of the polynomial, where the zero polynomial has degree `0`.
+    pub fn degree(&self) -> usize {
+        self.newton.len().saturating_sub(1)
+    }
+
+    /// Evaluates the polynomial at `index`, or [`None`] on overflow.
+    pub fn eval(&self, index: i64) -> Option<i128> {
+        let n = i128::from(index);
+        let mut binomial: i128 = 1;
+        let mut sum: i128 = 0;
+
+        for (k, diff) in (0i128..).zip(&self.newton) {
+            if k > 0 {
+                // C(n, k) = C(n, k - 1)·(n - k + 1) / k, where the division is always exact.
+                binomial = binomial.checked_mul(n - k + 1)? / k;
+            }
+            sum = sum.checked_add(diff.checked_mul(binomial)?)?;
+        }
+
+        Some(sum)
+    }
+
+    /// The coefficients in the usual basis, starting with the constant term, or [`None`]
+    /// on overflow.
+    ///
+    /// ```
+    /// # use advent_of_code::poly::{Poly, Rational};
+    /// // the triangular numbers are n²/2 + n/2.
+    /// let coefficients = Poly::fit(&[0, 1, 3, 6, 10]).unwrap().coefficients().unwrap();
+    /// let half = Rational::new(1, 2).unwrap();
+    /// assert_eq!(coefficients, vec![Rational::ZERO, half, half]);
+    /// ```
+    pub fn coefficients(&self) -> Option<Vec<Rational>> {
+        let mut coefficients = vec![Rational::ZERO; self.newton.len().max(1)];
+        // the falling factorial n·(n - 1)···(n - k + 1) in the usual basis, and k!.
+        let mut falling: Vec<i128> = vec![1];
+        let mut factorial: i128 = 1;
+
+        for (k, diff) in (0i128..).zip(&self.newton) {
+            if k > 0 {
+                factorial = factorial.checked_mul(k)?;
+                let mut next: Vec<i128> = vec![0; falling.len() + 1];
+                for (i, c) in falling.iter().enumerate() {
+                    next[i + 1] = next[i + 1].checked_add(*c)?;
+                    next[i] = next[i].checked_sub(c.checked_mul(k - 1)?)?;
+                }
+                falling = next;
+            }
+            for (coefficient, c) in coefficients.iter_mut().zip(&falling) {
+                let term = Rational::new(diff.checked_mul(*c)?, factorial)?;
+                *coefficient = coefficient.checked_add(&term)?;
+            }
+        }
+
+        Some(coefficients)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Poly, PolyError, Rational};
+
+    fn samples(f: impl Fn(i64) -> i64, count: i64) -> Vec<i64> {
+        (0..count).map(f).collect()
+    }
+
+    #[test]
+    fn fits_degree() {
+        assert_eq!(Poly::fit(&[0, 0, 0]).unwrap().degree(), 0);
+        assert_eq!(Poly::fit(&[7, 7]).unwrap().degree(), 0);
+        assert_eq!(Poly::fit(&[0, 3, 6, 9, 12, 15]).unwrap().degree(), 1);
+        assert_eq!(Poly::fit(&[1, 3, 6, 10, 15, 21]).unwrap().degree(), 2);
+        let cubic = samples(|n| 2 * n * n * n - 5 * n + 1, 8);
+        assert_eq!(Poly::fit(&cubic).unwrap().degree(), 3);
+    }
+
+    #[test]
+    fn evaluates_far_off_indices() {
+        let poly = Poly::fit(&samples(|n| 3 * n * n - 7 * n + 2, 10)).unwrap();
+        let n: i128 = 26_501_365;
+        assert_eq!(poly.eval(26_501_365), Some(3 * n * n - 7 * n + 2));
+        assert_eq!(poly.eval(-1), Some(12));
+        assert_eq!(poly.eval(-1_000_000), Some(3_000_007_000_002));
+    }
+
+    #[test]
+    fn matches_day_nine_example() {
+        let next: Vec<_> = [
+            vec![0, 3, 6, 9, 12, 15],
+            vec![1, 3, 6, 10, 15, 21],
+            vec![10, 13, 16, 21, 30, 45],
+        ]
+        .iter()
+        .map(|s| {
+            let poly = Poly::fit(s).unwrap();
+            (poly.eval(s.len() as i64).unwrap(), poly.eval(-1).unwrap())
+        })
+        .collect();
+        assert_eq!(next, vec![(18, -3), (28, 0), (68, 5)]);
+    }
+
+    #[test]
+    fn detects_non_polynomials() {
+        assert_eq!(Poly::fit(&[]), Err(PolyError::Empty));
+        assert_eq!(Poly::fit(&[5]), Err(PolyError::NotPolynomial));
+        assert_eq!(
+            Poly::fit(&[1, 2, 4, 8, 16, 32]),
+            Err(PolyError::NotPolynomial)
+        );
+        assert_eq!(
+            Poly::fit(&[1, 1, 2, 3, 5, 8, 13]),
+            Err(PolyError::NotPolynomial)
+        );
+        // a quadratic needs a fourth sample to be confirmed.
+        assert_eq!(Poly::fit(&[1, 3, 6]), Err(PolyError::NotPolynomial));
+        assert!(Poly::fit(&[1, 3, 6, 10]).is_ok());
+    }
+
+    #[test]
+    fn interpolates_unconfirmed_samples() {
+        assert_eq!(Poly::interpolate(&[]), Err(PolyError::Empty));
+        let constant = Poly::interpolate(&[5]).unwrap();
+        assert_eq!((constant.degree(), constant.eval(-3)), (0, Some(5)));
+
+        let quadratic = Poly::interpolate(&[1, 3, 6]).unwrap();
+        assert_eq!(quadratic.degree(), 2);
+        assert_eq!((quadratic.eval(3), quadratic.eval(-1)), (Some(10), Some(0)));
+
+        // confirmed samples give the same polynomial as fitting.
+        let samples = samples(|n| n * n * n - n, 7);
+        assert_eq!(Poly::interpolate(&samples), Poly::fit(&samples));
+    }
+
+    #[test]
+    fn reports_overflow() {
+        assert_eq!(
+            Poly::fit(&samples(
+                |n| if n % 2 == 0 { i64::MAX } else { i64::MIN },
+                80
+            )),
+            Err(PolyError::Overflow)
+        );
+        let poly = Poly::fit(&samples(|n| n * n * n, 5)).unwrap();
+        assert_eq!(poly.eval(i64::MAX), None);
+    }
+
+    #[test]
+    fn converts_to_coefficients() {
+        let poly = Poly::fit(&samples(|n| 2 * n * n * n - 5 * n + 1, 6)).unwrap();
+        let coefficients: Vec<_> = poly
+            .coefficients()
+            .unwrap()
+            .iter()
+            .map(|c| c.to_integer().unwrap())
+            .collect();
+        assert_eq!(coefficients, vec![1, -5, 0, 2]);
+
+        let poly = Poly::fit(&[0, 0, 0]).unwrap();
+        assert_eq!(poly.coefficients().unwrap(), vec![Rational::ZERO]);
+        assert_eq!(poly.eval(12), Some(0));
+    }
+
+    #[test]
+    fn normalizes_rationals() {
+        let r = Rational::new(6, -4).unwrap();
+        assert_eq!((r.numer(), r.denom()), (-3, 2));
+        assert_eq!(r.to_string(), "-3/2");
+        assert_eq!(Rational::new(1, 0), None);
+        let sum = r.checked_add(&Rational::new(3, 2).unwrap()).unwrap();
+        assert_eq!(sum, Rational::ZERO);
+    }
+}
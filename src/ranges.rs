@@ -0,0 +1,369 @@
+use std::cmp::{max, min};
+use std::error::Error;
+use std::fmt::Display;
+use std::ops::Range;
+
+/// A set of integers stored as sorted, disjoint, non-adjacent half-open intervals.
+///
+/// ```
+/// # use advent_of_code::ranges::IntervalSet;
+/// let a: IntervalSet = [0..10, 20..30].into_iter().collect();
+/// let b: IntervalSet = [5..25].into_iter().collect();
+/// assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25]);
+/// assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct IntervalSet {
+    ranges: Vec<Range<i64>>,
+}
+
+impl IntervalSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sorts and merges ranges, dropping empty ones.
+    fn normalize(mut ranges: Vec<Range<i64>>) -> Self {
+        ranges.retain(|r| r.start < r.end);
+        ranges.sort_unstable_by_key(|r| r.start);
+
+        let mut merged: Vec<Range<i64>> = Vec::with_capacity(ranges.len());
+        for range in ranges {
+            match merged.last_mut() {
+                Some(last) if range.start <= last.end => last.end = max(last.end, range.end),
+                _ => merged.push(range),
+            }
+        }
+
+        Self { ranges: merged }
+    }
+
+    /// The disjoint intervals of this set in ascending order.
+    pub fn ranges(&self) -> &[Range<i64>] {
+        &self.ranges
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The number of integers in this set.
+    pub fn count(&self) -> u64 {
+        self.ranges.iter().map(|r| r.start.abs_diff(r.end)).sum()
+    }
+
+    /// The smallest integer in this set.
+    pub fn min(&self) -> Option<i64> {
+        self.ranges.first().map(|r| r.start)
+    }
+
+    /// The largest integer in this set.
+    pub fn max(&self) -> Option<i64> {
+        self.ranges.last().map(|r| r.end - 1)
+    }
+
+    pub fn contains(&self, value: i64) -> bool {
+        let i = self.ranges.partition_point(|r| r.end <= value);
+        self.ranges.get(i).is_some_and(|r| r.start <= value)
+    }
+
+    pub fn insert(&mut self, range: Range<i64>) {
+        let mut ranges = std::mem::take(&mut self.ranges);
+        ranges.push(range);
+        *self = Self::normalize(ranges);
+    }
+
+    pub fn union(&self, other: &Self) -> Self {
+        Self::normalize(self.ranges.iter().chain(&other.ranges).cloned().collect())
+    }
+
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut result = vec![];
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            let start = max(a.start, b.start);
+            let end = min(a.end, b.end);
+            if start < end {
+                result.push(start..end);
+            }
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges: result }
+    }
+
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut result = vec![];
+        let mut j = 0;
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // skip subtrahends that end before this range.
+            while other.ranges.get(j).is_some_and(|b| b.end <= start) {
+                j += 1;
+            }
+
+            let mut k = j;
+            while let Some(b) = other.ranges.get(k) {
+                if b.start >= range.end {
+                    break;
+                }
+                if b.start > start {
+                    result.push(start..b.start);
+                }
+                start = max(start, b.end);
+                k += 1;
+            }
+
+            if start < range.end {
+                result.push(start..range.end);
+            }
+        }
+
+        Self { ranges: result }
+    }
+}
+
+impl FromIterator<Range<i64>> for IntervalSet {
+    fn from_iter<I: IntoIterator<Item = Range<i64>>>(iter: I) -> Self {
+        Self::normalize(iter.into_iter().collect())
+    }
+}
+
+impl From<Range<i64>> for IntervalSet {
+    fn from(range: Range<i64>) -> Self {
+        Self::normalize(vec![range])
+    }
+}
+
+/// An error which can be returned when adding a source range to a [`RangeMap`] that overlaps
+/// one of its ranges.
+#[derive(Debug, PartialEq, Eq)]
+pub struct OverlapError {
+    pub source: Range<i64>,
+    pub existing: Range<i64>,
+}
+
+impl Error for OverlapError {}
+
+impl Display for OverlapError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "range {:?} overlaps {:?}", self.source, self.existing)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise-linear map that shifts the values of each source range by an offset
+/// and maps every value outside of them to itself.
+///
+/// ```
+/// # use advent_of_code::ranges::{IntervalSet, RangeMap};
+/// // `50 98 2` and `52 50 48` from the day 5 example.
+/// let map: RangeMap = [(98..100, -48), (50..98, 2)].into_iter().collect();
+/// assert_eq!(map.map(79), 81);
+/// assert_eq!(map.map(10), 10);
+/// assert_eq!(map.map_set(&(95..101).into()).ranges(), &[50..52, 97..101]);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub struct RangeMap {
+    /// Source ranges with their offsets, sorted by start and non-overlapping.
+    entries: Vec<(Range<i64>, i64)>,
+}
+
+impl RangeMap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a source range whose values are shifted by `offset`.
+    ///
+    /// # Panics
+    /// Panics if `source` overlaps a range that was inserted before, see [`Self::try_insert`].
+    pub fn insert(&mut self, source: Range<i64>, offset: i64) {
+        if let Err(err) = self.try_insert(source, offset) {
+            panic!("{err}");
+        }
+    }
+
+    /// Adds a source range whose values are shifted by `offset`, unless it overlaps a range
+    /// that was inserted before. The map is left unchanged on error.
+    pub fn try_insert(&mut self, source: Range<i64>, offset: i64) -> Result<(), OverlapError> {
+        if source.start >= source.end {
+            return Ok(());
+        }
+
+        let i = self.entries.partition_point(|(r, _)| r.end <= source.start);
+        if let Some((existing, _)) = self.entries.get(i) {
+            if existing.start < source.end {
+                return Err(OverlapError {
+                    source,
+                    existing: existing.clone(),
+                });
+            }
+        }
+
+        self.entries.insert(i, (source, offset));
+        Ok(())
+    }
+
+    /// Maps a single value.
+    pub fn map(&self, value: i64) -> i64 {
+        let i = self.entries.partition_point(|(r, _)| r.end <= value);
+        match self.entries.get(i) {
+            Some((range, offset)) if range.start <= value => value + offset,
+            _ => value,
+        }
+    }
+
+    /// Maps a whole range at once by splitting it on the boundaries of the source ranges.
+    pub fn map_range(&self, range: Range<i64>) -> IntervalSet {
+        let mut pieces = vec![];
+        self.push_pieces(&range, &mut pieces);
+        IntervalSet::normalize(pieces)
+    }
+
+    /// Maps every interval of `set`, see [`RangeMap::map_range`].
+    pub fn map_set(&self, set: &IntervalSet) -> IntervalSet {
+        let mut pieces = vec![];
+        for range in set.ranges() {
+            self.push_pieces(range, &mut pieces);
+        }
+        IntervalSet::normalize(pieces)
+    }
+
+    fn push_pieces(&self, range: &Range<i64>, pieces: &mut Vec<Range<i64>>) {
+        let mut cursor = range.start;
+        let first = self.entries.partition_point(|(r, _)| r.end <= cursor);
+
+        for (source, offset) in &self.entries[first..] {
+            if source.start >= range.end {
+                break;
+            }
+            if cursor < source.start {
+                pieces.push(cursor..source.start);
+            }
+            let start = max(cursor, source.start);
+            let end = min(range.end, source.end);
+            pieces.push(start + offset..end + offset);
+            cursor = end;
+        }
+
+        if cursor < range.end {
+            pieces.push(cursor..range.end);
+        }
+    }
+}
+
+impl FromIterator<(Range<i64>, i64)> for RangeMap {
+    fn from_iter<I: IntoIterator<Item = (Range<i64>, i64)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, offset) in iter {
+            map.insert(source, offset);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{IntervalSet, OverlapError, RangeMap};
+
+    fn set(ranges: &[std::ops::Range<i64>]) -> IntervalSet {
+        ranges.iter().cloned().collect()
+    }
+
+    #[test]
+    fn normalizes_ranges() {
+        let s = set(&[10..20, 0..5, 5..7, 15..25, 30..30]);
+        assert_eq!(s.ranges(), &[0..7, 10..25]);
+        assert_eq!(s.count(), 22);
+        assert_eq!((s.min(), s.max()), (Some(0), Some(24)));
+    }
+
+    #[test]
+    fn contains() {
+        let s = set(&[0..5, 10..20]);
+        assert!(s.contains(0));
+        assert!(s.contains(4));
+        assert!(!s.contains(5));
+        assert!(s.contains(19));
+        assert!(!s.contains(20));
+        assert!(!s.contains(-1));
+    }
+
+    #[test]
+    fn set_algebra() {
+        let a = set(&[0..10, 20..30, 40..50]);
+        let b = set(&[5..25, 45..60]);
+        assert_eq!(a.union(&b).ranges(), &[0..30, 40..60]);
+        assert_eq!(a.intersection(&b).ranges(), &[5..10, 20..25, 45..50]);
+        assert_eq!(a.difference(&b).ranges(), &[0..5, 25..30, 40..45]);
+        assert_eq!(b.difference(&a).ranges(), &[10..20, 50..60]);
+        assert!(a.difference(&a).is_empty());
+    }
+
+    #[test]
+    fn difference_with_many_holes() {
+        let a = set(&[0..100]);
+        let b = set(&[-5..1, 10..20, 30..40, 99..120]);
+        assert_eq!(a.difference(&b).ranges(), &[1..10, 20..30, 40..99]);
+    }
+
+    #[test]
+    fn insert_merges() {
+        let mut s = set(&[0..5]);
+        s.insert(5..8);
+        s.insert(20..21);
+        assert_eq!(s.ranges(), &[0..8, 20..21]);
+    }
+
+    #[test]
+    fn maps_values_and_ranges() {
+        let map: RangeMap = [(98..100, -48), (50..98, 2)].into_iter().collect();
+        assert_eq!(map.map(98), 50);
+        assert_eq!(map.map(99), 51);
+        assert_eq!(map.map(100), 100);
+        assert_eq!(map.map(49), 49);
+        assert_eq!(map.map_range(79..93).ranges(), &[81..95]);
+        assert_eq!(map.map_range(40..60).ranges(), &[40..50, 52..62]);
+        assert_eq!(map.map_range(90..110).ranges(), &[50..52, 92..110]);
+    }
+
+    #[test]
+    fn maps_sets() {
+        let map: RangeMap = [(0..10, 100), (20..30, -20)].into_iter().collect();
+        let mapped = map.map_set(&set(&[5..25, 40..41]));
+        assert_eq!(mapped.ranges(), &[0..5, 10..20, 40..41, 105..110]);
+        assert_eq!(mapped.count(), 21);
+    }
+
+    #[test]
+    #[should_panic]
+    fn rejects_overlapping_entries() {
+        let _: RangeMap = [(0..10, 1), (5..15, 2)].into_iter().collect();
+    }
+
+    #[test]
+    fn try_insert_reports_overlaps() {
+        let mut map: RangeMap = [(0..10, 1)].into_iter().collect();
+        assert_eq!(
+            map.try_insert(5..15, 2),
+            Err(OverlapError {
+                source: 5..15,
+                existing: 0..10
+            })
+        );
+        assert_eq!(map.try_insert(10..15, 2), Ok(()));
+        assert_eq!(map.map(12), 14);
+        assert_eq!(map.map(5), 6);
+    }
+}
@@ -0,0 +1,321 @@
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap, VecDeque};
+use std::hash::Hash;
+use std::ops::Add;
+
+/// The outcome of a successful search: the cost of reaching the goal and the search tree
+/// to reconstruct the path with.
+///
+/// ```
+/// # use advent_of_code::search::bfs;
+/// let found = bfs(1u32, |n| [n + 1, n * 2], |n| *n == 10).unwrap();
+/// assert_eq!(found.cost, 4);
+/// assert_eq!(found.path(), vec![1, 2, 4, 5, 10]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Found<S, C> {
+    pub cost: C,
+    goal: usize,
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<S: Clone, C> Found<S, C> {
+    /// The state that satisfied the goal.
+    pub fn goal(&self) -> &S {
+        &self.states[self.goal]
+    }
+
+    /// The states from the start to the goal, both included.
+    pub fn path(&self) -> Vec<S> {
+        let mut path = vec![];
+        let mut current = Some(self.goal);
+        while let Some(i) = current {
+            path.push(self.states[i].clone());
+            current = self.parents[i];
+        }
+        path.reverse();
+        path
+    }
+}
+
+/// Every state seen so far, numbered in discovery order, with the state it was reached from.
+struct Visited<S> {
+    index: HashMap<S, usize>,
+    states: Vec<S>,
+    parents: Vec<Option<usize>>,
+}
+
+impl<S: Clone + Eq + Hash> Visited<S> {
+    fn new(start: S) -> Self {
+        Self {
+            index: HashMap::from([(start.clone(), 0)]),
+            states: vec![start],
+            parents: vec![None],
+        }
+    }
+
+    /// Returns the id of `state`, adding it with the given parent if it is new.
+    fn insert(&mut self, state: S, parent: usize) -> (usize, bool) {
+        if let Some(&i) = self.index.get(&state) {
+            return (i, false);
+        }
+        let i = self.states.len();
+        self.index.insert(state.clone(), i);
+        self.states.push(state);
+        self.parents.push(Some(parent));
+        (i, true)
+    }
+
+    fn found<C>(self, cost: C, goal: usize) -> Found<S, C> {
+        Found {
+            cost,
+            goal,
+            states: self.states,
+            parents: self.parents,
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Breadth-first search for the goal closest to `start` in number of steps.
+pub fn bfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new(start);
+    let mut queue = VecDeque::from([(0, 0)]);
+
+    while let Some((i, steps)) = queue.pop_front() {
+        if goal(&visited.states[i]) {
+            return Some(visited.found(steps, i));
+        }
+        for next in neighbours(&visited.states[i]) {
+            if let (j, true) = visited.insert(next, i) {
+                queue.push_back((j, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Breadth-first search over everything reachable from `start`, returning the number of
+/// steps to each state.
+pub fn bfs_distances<S, I>(start: S, mut neighbours: impl FnMut(&S) -> I) -> HashMap<S, usize>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut distances = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([(start, 0)]);
+
+    while let Some((state, steps)) = queue.pop_front() {
+        for next in neighbours(&state) {
+            if !distances.contains_key(&next) {
+                distances.insert(next.clone(), steps + 1);
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+
+    distances
+}
+
+/// Depth-first search for any goal reachable from `start`. The cost is the length of the
+/// path that was found, which need not be the shortest.
+pub fn dfs<S, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, usize>>
+where
+    S: Clone + Eq + Hash,
+    I: IntoIterator<Item = S>,
+{
+    let mut visited = Visited::new(start);
+    let mut stack = vec![(0, 0)];
+
+    while let Some((i, steps)) = stack.pop() {
+        if goal(&visited.states[i]) {
+            return Some(visited.found(steps, i));
+        }
+        for next in neighbours(&visited.states[i]) {
+            if let (j, true) = visited.insert(next, i) {
+                stack.push((j, steps + 1));
+            }
+        }
+    }
+
+    None
+}
+
+/// Dijkstra's algorithm for the cheapest goal, where `neighbours` yields each next state
+/// with the non-negative cost of moving there.
+pub fn dijkstra<S, C, I>(
+    start: S,
+    neighbours: impl FnMut(&S) -> I,
+    goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    astar(start, neighbours, |_| C::default(), goal)
+}
+
+/// A* search for the cheapest goal, see [`dijkstra`]. The `heuristic` estimates the
+/// remaining cost to a goal and must never overestimate it for the result to be optimal.
+pub fn astar<S, C, I>(
+    start: S,
+    mut neighbours: impl FnMut(&S) -> I,
+    mut heuristic: impl FnMut(&S) -> C,
+    mut goal: impl FnMut(&S) -> bool,
+) -> Option<Found<S, C>>
+where
+    S: Clone + Eq + Hash,
+    C: Copy + Ord + Default + Add<Output = C>,
+    I: IntoIterator<Item = (S, C)>,
+{
+    let zero = C::default();
+    let mut queue = BinaryHeap::from([Reverse((heuristic(&start), zero, 0))]);
+    let mut visited = Visited::new(start);
+    let mut best = vec![zero];
+
+    while let Some(Reverse((_, cost, i))) = queue.pop() {
+        // skip stale entries that were superseded by a cheaper route.
+        if cost > best[i] {
+            continue;
+        }
+        if goal(&visited.states[i]) {
+            return Some(visited.found(cost, i));
+        }
+        for (next, step) in neighbours(&visited.states[i]) {
+            let next_cost = cost + step;
+            let estimate = next_cost + heuristic(&next);
+            let (j, is_new) = visited.insert(next, i);
+            if is_new {
+                best.push(next_cost);
+            } else if next_cost < best[j] {
+                best[j] = next_cost;
+                visited.parents[j] = Some(i);
+            } else {
+                continue;
+            }
+            queue.push(Reverse((estimate, next_cost, j)));
+        }
+    }
+
+    None
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{astar, bfs, bfs_distances, dfs, dijkstra};
+    use crate::grid::{Grid, Pos};
+
+    const MAZE: &str = "\
+S.#.....
+.##.###.
+....#...
+.##.#.#.
+....#.#E";
+
+    fn maze() -> Grid<char> {
+        MAZE.parse().unwrap()
+    }
+
+    fn open(grid: &Grid<char>, pos: Pos) -> Vec<Pos> {
+        grid.neighbors4(pos).filter(|p| grid[*p] != '#').collect()
+    }
+
+    #[test]
+    fn bfs_finds_shortest_path() {
+        let grid = maze();
+        let found = bfs((0, 0), |p| open(&grid, *p), |p| grid[*p] == 'E').unwrap();
+        assert_eq!(found.cost, 15);
+        assert_eq!(*found.goal(), (7, 4));
+
+        let path = found.path();
+        assert_eq!(path.len(), 16);
+        assert_eq!(path[0], (0, 0));
+        assert!(path
+            .windows(2)
+            .all(|w| w[0].0.abs_diff(w[1].0) + w[0].1.abs_diff(w[1].1) == 1));
+    }
+
+    #[test]
+    fn bfs_distances_flood_fill() {
+        let grid = maze();
+        let distances = bfs_distances((0, 0), |p| open(&grid, *p));
+        assert_eq!(distances.len(), grid.find_all(|c| *c != '#').count());
+        assert_eq!(distances[&(7, 4)], 15);
+        assert_eq!(distances[&(3, 0)], 7);
+    }
+
+    #[test]
+    fn dfs_finds_some_path() {
+        let grid = maze();
+        let found = dfs((0, 0), |p| open(&grid, *p), |p| grid[*p] == 'E').unwrap();
+        assert!(found.cost >= 15);
+        assert_eq!(found.path().len(), found.cost + 1);
+    }
+
+    #[test]
+    fn unreachable_goal() {
+        let grid = maze();
+        assert!(bfs((0, 0), |p| open(&grid, *p), |p| *p == (2, 0)).is_none());
+        assert!(dfs((0, 0), |p| open(&grid, *p), |p| *p == (2, 0)).is_none());
+    }
+
+    #[test]
+    fn dijkstra_prefers_cheap_edges() {
+        // a -1- b -1- c -1- d is cheaper than a -5- d.
+        let edges = |n: &char| match n {
+            'a' => vec![('d', 5u32), ('b', 1)],
+            'b' => vec![('c', 1)],
+            'c' => vec![('d', 1)],
+            _ => vec![],
+        };
+        let found = dijkstra('a', edges, |n| *n == 'd').unwrap();
+        assert_eq!(found.cost, 3);
+        assert_eq!(found.path(), vec!['a', 'b', 'c', 'd']);
+    }
+
+    #[test]
+    fn astar_matches_dijkstra() {
+        let grid: Grid<u32> = Grid::parse_with(
+            "2413432311323\n3215453535623\n3255245654254\n3446585845452\n4546657867536",
+            |c| c.to_digit(10).unwrap(),
+        )
+        .unwrap();
+        let target = (grid.width() - 1, grid.height() - 1);
+        let edges = |p: &Pos| {
+            grid.neighbors4(*p)
+                .map(|n| (n, grid[n]))
+                .collect::<Vec<_>>()
+        };
+
+        let plain = dijkstra((0, 0), edges, |p| *p == target).unwrap();
+        let guided = astar(
+            (0, 0),
+            edges,
+            |p| (target.0 - p.0 + target.1 - p.1) as u32,
+            |p| *p == target,
+        )
+        .unwrap();
+
+        assert_eq!(plain.cost, guided.cost);
+        let cost: u32 = guided.path()[1..].iter().map(|p| grid[*p]).sum();
+        assert_eq!(cost, guided.cost);
+    }
+}
@@ -0,0 +1,152 @@
+/// A global allocator that counts allocations while a solution part is measured.
+/// It is installed by the `solution!` macro and only counts while [`measure`] runs,
+/// so it costs a single atomic load per allocation otherwise.
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicBool, AtomicIsize, AtomicUsize, Ordering};
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static LIVE: AtomicIsize = AtomicIsize::new(0);
+static PEAK: AtomicIsize = AtomicIsize::new(0);
+
+pub struct CountingAllocator;
+
+/// Adds `delta` to the live bytes and returns the new count. Memory allocated before the
+/// measurement may be freed during it, so the count is clamped at zero rather than going
+/// negative and lowering every later high-water mark.
+fn add_live(delta: isize) -> isize {
+    let update = |live: isize| live.saturating_add(delta).max(0);
+    match LIVE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |live| {
+        Some(update(live))
+    }) {
+        Ok(previous) | Err(previous) => update(previous),
+    }
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_alloc(size: usize, freed: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size, Ordering::Relaxed);
+    let live = add_live(size as isize - freed as isize);
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+#[allow(clippy::cast_possible_wrap)]
+fn record_dealloc(size: usize) {
+    add_live(-(size as isize));
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_alloc(layout.size(), 0);
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        if ENABLED.load(Ordering::Relaxed) {
+            record_dealloc(layout.size());
+        }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() && ENABLED.load(Ordering::Relaxed) {
+            record_alloc(new_size, layout.size());
+        }
+        new_ptr
+    }
+}
+
+/// Allocation counts of a single measured run.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct AllocStats {
+    /// Number of allocations, including reallocations.
+    pub allocations: usize,
+    /// Total bytes requested over the run.
+    pub bytes: usize,
+    /// Highest amount of bytes that were allocated at the same time during the run.
+    pub peak_bytes: usize,
+}
+
+/// Runs `func` while counting its allocations.
+/// Only meaningful in binaries that install [`CountingAllocator`], i.e. solutions using `solution!`.
+pub fn measure<T>(func: impl FnOnce() -> T) -> (T, AllocStats) {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    LIVE.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+
+    ENABLED.store(true, Ordering::SeqCst);
+    let result = func();
+    ENABLED.store(false, Ordering::SeqCst);
+
+    let stats = AllocStats {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak_bytes: usize::try_from(PEAK.load(Ordering::Relaxed)).unwrap_or(0),
+    };
+
+    (result, stats)
+}
+
+/// Formats a byte count with a binary unit, e.g. `3.4 KiB`.
+#[must_use]
+pub fn format_bytes(bytes: usize) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    #[allow(clippy::cast_precision_loss)]
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, measure, CountingAllocator};
+    use std::hint::black_box;
+
+    #[cfg(test)]
+    #[global_allocator]
+    static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1024), "1.0 KiB");
+        assert_eq!(format_bytes(3482), "3.4 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn peak_ignores_frees_of_earlier_allocations() {
+        let earlier = black_box(vec![0u8; 1 << 20]);
+        let ((), stats) = measure(|| {
+            drop(earlier);
+            black_box(vec![0u8; 4096]);
+        });
+        assert!(stats.peak_bytes >= 4096);
+    }
+}
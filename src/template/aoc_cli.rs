@@ -74,12 +74,20 @@ pub fn download(day: Day) -> Result<Output, AocCommandError> {
     Ok(output)
 }
 
+/// Submits an answer and echoes the response. aoc-cli exits successfully for wrong answers
+/// too, see [`is_right_answer`] for whether the answer was accepted.
 pub fn submit(day: Day, part: u8, result: &str) -> Result<Output, AocCommandError> {
     // workaround: the argument order is inverted for submit.
     let mut args = build_args("submit", &[], day);
     args.push(part.to_string());
     args.push(result.to_string());
-    call_aoc_cli(&args)
+    call_aoc_cli_captured(&args)
+}
+
+/// Whether the response to a submission says the answer was right.
+#[must_use]
+pub fn is_right_answer(output: &Output) -> bool {
+    String::from_utf8_lossy(&output.stdout).contains("That's the right answer")
 }
 
 fn get_input_path(day: Day) -> String {
@@ -119,6 +127,23 @@ fn call_aoc_cli(args: &[String]) -> Result<Output, AocCommandError> {
         .output()
         .map_err(|_| AocCommandError::CommandNotCallable)?;
 
+    check_status(output)
+}
+
+/// Like [`call_aoc_cli`], but keeps a copy of stdout in the returned output.
+fn call_aoc_cli_captured(args: &[String]) -> Result<Output, AocCommandError> {
+    let output = Command::new("aoc")
+        .args(args)
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .output()
+        .map_err(|_| AocCommandError::CommandNotCallable)?;
+
+    print!("{}", String::from_utf8_lossy(&output.stdout));
+    check_status(output)
+}
+
+fn check_status(output: Output) -> Result<Output, AocCommandError> {
     if output.status.success() {
         Ok(output)
     } else {
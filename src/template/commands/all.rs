@@ -1,13 +1,40 @@
-use std::io;
+use std::{io, process};
 
 use crate::template::{
+    alloc::{format_bytes, AllocStats},
+    export::{self, Format},
     readme_benchmarks::{self, Timings},
     ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
 };
 use crate::{all_days, Day};
 
-pub fn handle(is_release: bool, is_timed: bool) {
+pub fn handle(
+    is_release: bool,
+    is_timed: bool,
+    is_allocs: bool,
+    format: Option<Format>,
+    output: Option<String>,
+) {
+    let export_format = match (format, output.as_deref()) {
+        (Some(format), _) => Some(format),
+        (None, Some(path)) => {
+            let Some(format) = infer_format(path) else {
+                eprintln!(
+                    "Error: cannot infer an export format from \"{path}\". Use a .json, .csv or .md file, or pass --format <json|csv|markdown>."
+                );
+                process::exit(1);
+            };
+            Some(format)
+        }
+        (None, None) => None,
+    };
+
+    if export_format.is_some() && !is_timed {
+        eprintln!("Warning: exporting benchmarks requires the `--time` flag.");
+    }
+
     let mut timings: Vec<Timings> = vec![];
+    let mut allocs = AllocStats::default();
 
     all_days().for_each(|day| {
         if day > 1 {
@@ -17,21 +44,48 @@ pub fn handle(is_release: bool, is_timed: bool) {
         println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
         println!("------");
 
-        let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+        let output = child_commands::run_solution(day, is_timed, is_allocs, is_release).unwrap();
 
         if output.is_empty() {
             println!("Not solved.");
         } else {
             let val = child_commands::parse_exec_time(&output, day);
             timings.push(val);
+
+            let day_allocs = child_commands::parse_allocs(&output);
+            allocs.allocations += day_allocs.allocations;
+            allocs.bytes += day_allocs.bytes;
+            allocs.peak_bytes = allocs.peak_bytes.max(day_allocs.peak_bytes);
         }
     });
 
+    if is_allocs {
+        println!(
+            "\n{ANSI_BOLD}Total allocations:{ANSI_RESET} {ANSI_ITALIC}{} ({} allocated, {} peak){ANSI_RESET}",
+            allocs.allocations,
+            format_bytes(allocs.bytes),
+            format_bytes(allocs.peak_bytes)
+        );
+    }
+
     if is_timed {
         let total_millis = timings.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64;
 
         println!("\n{ANSI_BOLD}Total:{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}");
 
+        if let Some(format) = export_format {
+            match export::export(format, output.as_deref(), &timings, total_millis) {
+                Ok(()) => {
+                    if let Some(path) = &output {
+                        println!("Successfully exported benchmarks to \"{path}\".");
+                    }
+                }
+                Err(e) => {
+                    eprintln!("Failed to export benchmarks: {e}");
+                }
+            }
+        }
+
         if is_release {
             match readme_benchmarks::update(timings, total_millis) {
                 Ok(()) => println!("Successfully updated README with benchmarks."),
@@ -39,6 +93,13 @@ pub fn handle(is_release: bool, is_timed: bool) {
                     eprintln!("Failed to update readme with benchmarks.");
                 }
             }
+
+            match readme_benchmarks::update_progress(&readme_benchmarks::get_progress()) {
+                Ok(()) => println!("Successfully updated README with progress."),
+                Err(_) => {
+                    eprintln!("Failed to update readme with progress.");
+                }
+            }
         }
     }
 }
@@ -61,10 +122,17 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
+/// Picks an export format from the output file's extension, if it is a known one.
+fn infer_format(path: &str) -> Option<Format> {
+    path.rsplit_once('.').and_then(|(_, ext)| ext.parse().ok())
+}
+
 /// All solutions live in isolated binaries.
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 mod child_commands {
     use super::{get_path_for_bin, Error};
+    use crate::template::alloc::AllocStats;
+    use crate::template::readme_benchmarks::{PartTimings, Stats, Timings};
     use crate::Day;
     use std::{
         io::{BufRead, BufReader},
@@ -74,7 +142,12 @@ mod child_commands {
     };
 
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        is_allocs: bool,
+        is_release: bool,
+    ) -> Result<Vec<String>, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
             return Ok(vec![]);
@@ -87,12 +160,18 @@ mod child_commands {
             args.push("--release");
         }
 
+        args.push("--");
+
         if is_timed {
             // mirror `--time` flag to child invocations.
-            args.push("--");
             args.push("--time");
         }
 
+        if is_allocs {
+            // mirror `--allocs` flag to child invocations.
+            args.push("--allocs");
+        }
+
         // spawn child command with piped stdout/stderr.
         // forward output to stdout/stderr while grabbing stdout lines.
 
@@ -125,65 +204,156 @@ mod child_commands {
         Ok(output)
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timings {
-        let mut timings = super::Timings {
+    pub fn parse_exec_time(output: &[String], day: Day) -> Timings {
+        let mut timings = Timings {
             day,
             part_1: None,
             part_2: None,
             total_nanos: 0_f64,
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
-
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
+        for l in output {
+            let Some((part, rest)) = l.split_once(':') else {
+                continue;
+            };
+
+            let slot = if part.contains("Part 1") {
+                &mut timings.part_1
+            } else if part.contains("Part 2") {
+                &mut timings.part_2
+            } else {
+                continue;
+            };
+
+            if part.ends_with(" stats") {
+                let Some(stats) = parse_stats(rest) else {
+                    eprintln!("Could not parse stats from line: {l}");
+                    continue;
                 };
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
+                if let Some(part_timings) = slot {
+                    part_timings.stats = Some(stats);
                 }
+            } else if l.contains(" samples)") {
+                let Some((timing_str, nanos, samples)) = parse_time(l) else {
+                    eprintln!("Could not parse timings from line: {l}");
+                    continue;
+                };
 
+                *slot = Some(PartTimings::new(timing_str, nanos, samples));
                 timings.total_nanos += nanos;
-            });
+            }
+        }
 
         timings
     }
 
+    /// Sums the allocation counts reported for all parts of a day.
+    /// The peak is the highest peak of any single part.
+    pub fn parse_allocs(output: &[String]) -> AllocStats {
+        let mut allocs = AllocStats::default();
+
+        for l in output {
+            let Some((part, rest)) = l.split_once(':') else {
+                continue;
+            };
+
+            if !part.starts_with("Part ") || !part.ends_with(" allocs") {
+                continue;
+            }
+
+            let Some(part_allocs) = parse_alloc_line(rest) else {
+                eprintln!("Could not parse allocations from line: {l}");
+                continue;
+            };
+
+            allocs.allocations += part_allocs.allocations;
+            allocs.bytes += part_allocs.bytes;
+            allocs.peak_bytes = allocs.peak_bytes.max(part_allocs.peak_bytes);
+        }
+
+        allocs
+    }
+
+    fn parse_alloc_line(s: &str) -> Option<AllocStats> {
+        let mut fields = s.split(',').map(str::trim);
+
+        let allocations = fields.next()?.strip_suffix(" allocations")?.parse().ok()?;
+        let bytes = parse_bytes(fields.next()?.strip_suffix(" allocated")?)?;
+        let peak_bytes = parse_bytes(fields.next()?.strip_suffix(" peak")?)?;
+
+        Some(AllocStats {
+            allocations,
+            bytes,
+            peak_bytes,
+        })
+    }
+
+    fn parse_bytes(s: &str) -> Option<usize> {
+        let (value, unit) = s.split_once(' ')?;
+        let multiplier = match unit {
+            "B" => 1_f64,
+            "KiB" => 1024_f64,
+            "MiB" => 1024_f64 * 1024_f64,
+            "GiB" => 1024_f64 * 1024_f64 * 1024_f64,
+            "TiB" => 1024_f64 * 1024_f64 * 1024_f64 * 1024_f64,
+            _ => return None,
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        Some((value.parse::<f64>().ok()? * multiplier).round() as usize)
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    fn parse_duration(s: &str) -> Option<f64> {
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
+        match s {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
             s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
             s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
             s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
+        }
+    }
+
+    fn parse_time(line: &str) -> Option<(&str, f64, u128)> {
+        let (str_timing, str_samples) = line
+            .split(" samples)")
+            .next()?
+            .split('(')
+            .next_back()?
+            .split_once('@')?;
+
+        let str_timing = str_timing.trim();
+        let parsed_timing = parse_duration(str_timing)?;
+        let samples = str_samples.trim().parse().ok()?;
+
+        Some((str_timing, parsed_timing, samples))
+    }
 
-        Some((str_timing, parsed_timing))
+    fn parse_stats(s: &str) -> Option<Stats> {
+        let mut stats = Stats {
+            min_nanos: 0_f64,
+            median_nanos: 0_f64,
+            max_nanos: 0_f64,
+            stddev_nanos: 0_f64,
+        };
+
+        for field in s.split(',') {
+            let (name, value) = field.trim().split_once(' ')?;
+            let nanos = parse_duration(value.trim())?;
+            match name {
+                "min" => stats.min_nanos = nanos,
+                "median" => stats.median_nanos = nanos,
+                "max" => stats.max_nanos = nanos,
+                "stddev" => stats.stddev_nanos = nanos,
+                _ => return None,
+            }
+        }
+
+        Some(stats)
     }
 
     /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
@@ -202,7 +372,7 @@ mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_allocs, parse_exec_time};
 
         use crate::day;
 
@@ -217,8 +387,8 @@ mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
+            assert_eq!(res.part_1.unwrap().timing, "74.13ns");
+            assert_eq!(res.part_2.unwrap().timing, "74.13ms");
         }
 
         #[test]
@@ -232,8 +402,8 @@ mod child_commands {
                 day!(1),
             );
             assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
+            assert_eq!(res.part_1.unwrap().timing, "2s");
+            assert_eq!(res.part_2.unwrap().timing, "100ms");
         }
 
         #[test]
@@ -250,5 +420,41 @@ mod child_commands {
             assert_eq!(res.part_1.is_none(), true);
             assert_eq!(res.part_2.is_none(), true);
         }
+
+        #[test]
+        fn test_samples_and_stats() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 1 stats: min 70.0ns, median 74.0ns, max 1.2µs, stddev 3.5ns".into(),
+                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
+                    "".into(),
+                ],
+                day!(1),
+            );
+            let part_1 = res.part_1.unwrap();
+            let stats = part_1.stats.unwrap();
+            assert_eq!(part_1.samples, 100000);
+            assert_approx_eq!(stats.min_nanos, 70_f64);
+            assert_approx_eq!(stats.median_nanos, 74_f64);
+            assert_approx_eq!(stats.max_nanos, 1200_f64);
+            assert_approx_eq!(stats.stddev_nanos, 3.5_f64);
+            assert_eq!(res.part_2.unwrap().stats.is_none(), true);
+            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
+        }
+
+        #[test]
+        fn test_allocs() {
+            let res = parse_allocs(&[
+                "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                "Part 1 allocs: 12 allocations, 512 B allocated, 256 B peak".into(),
+                "Part 2: 10 (74.13ms)".into(),
+                "Part 2 allocs: 3 allocations, 1.5 KiB allocated, 1.0 KiB peak".into(),
+                "".into(),
+            ]);
+            assert_eq!(res.allocations, 15);
+            assert_eq!(res.bytes, 2048);
+            assert_eq!(res.peak_bytes, 1024);
+        }
     }
 }
@@ -2,4 +2,6 @@ pub mod all;
 pub mod download;
 pub mod read;
 pub mod scaffold;
+pub mod scale;
 pub mod solve;
+pub mod stars;
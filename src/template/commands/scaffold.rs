@@ -1,6 +1,6 @@
 use std::{
     fs::{File, OpenOptions},
-    io::Write,
+    io::{ErrorKind, Write},
     process,
 };
 
@@ -15,23 +15,6 @@ pub fn part_one(input: &str) -> Option<u32> {
 pub fn part_two(input: &str) -> Option<u32> {
     None
 }
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-
-    #[test]
-    fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
-        assert_eq!(result, None);
-    }
-}
 "#;
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
@@ -39,12 +22,18 @@ fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    // keep the contents of files that already exist, e.g. a downloaded input.
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
 pub fn handle(day: Day) {
     let input_path = format!("data/inputs/{day}.txt");
     let example_path = format!("data/examples/{day}.txt");
+    let manifest_path = format!("data/examples/{day}.manifest");
     let module_path = format!("src/bin/{day}.rs");
 
     let mut file = match safe_create_file(&module_path) {
@@ -89,6 +78,24 @@ pub fn handle(day: Day) {
         }
     }
 
+    match safe_create_file(&manifest_path) {
+        Ok(mut file) => {
+            let manifest = format!("# file        part 1   part 2\n{day}.txt        -        -\n");
+            if let Err(e) = file.write_all(manifest.as_bytes()) {
+                eprintln!("Failed to write example manifest: {e}");
+                process::exit(1);
+            }
+            println!("Created example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) if e.kind() == ErrorKind::AlreadyExists => {
+            println!("Kept existing example manifest \"{}\"", &manifest_path);
+        }
+        Err(e) => {
+            eprintln!("Failed to create example manifest: {e}");
+            process::exit(1);
+        }
+    }
+
     println!("---");
     println!("🎄 Type `cargo solve {}` to run your solution.", day);
 }
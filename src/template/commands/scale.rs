@@ -0,0 +1,25 @@
+use std::process::{Command, Stdio};
+
+use crate::Day;
+
+pub fn handle(day: Day) {
+    // scaling only makes sense for optimized builds.
+    let cmd_args = vec![
+        "run".to_string(),
+        "--quiet".to_string(),
+        "--release".to_string(),
+        "--bin".to_string(),
+        day.to_string(),
+        "--".to_string(),
+        "--scale".to_string(),
+    ];
+
+    let mut cmd = Command::new("cargo")
+        .args(&cmd_args)
+        .stdout(Stdio::inherit())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .unwrap();
+
+    cmd.wait().unwrap();
+}
@@ -2,7 +2,15 @@ use std::process::{Command, Stdio};
 
 use crate::Day;
 
-pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
+pub fn handle(
+    day: Day,
+    release: bool,
+    time: bool,
+    allocs: bool,
+    viz: bool,
+    dot: Option<String>,
+    submit_part: Option<u8>,
+) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
 
     if release {
@@ -20,6 +28,19 @@ pub fn handle(day: Day, release: bool, time: bool, submit_part: Option<u8>) {
         cmd_args.push("--time".to_string());
     }
 
+    if allocs {
+        cmd_args.push("--allocs".to_string());
+    }
+
+    if viz {
+        cmd_args.push("--viz".to_string());
+    }
+
+    if let Some(dot) = dot {
+        cmd_args.push("--dot".to_string());
+        cmd_args.push(dot);
+    }
+
     let mut cmd = Command::new("cargo")
         .args(&cmd_args)
         .stdout(Stdio::inherit())
@@ -0,0 +1,19 @@
+use std::process;
+
+use crate::template::readme_benchmarks;
+
+pub fn handle() {
+    let progress = readme_benchmarks::get_progress();
+    let stars: usize = progress
+        .iter()
+        .map(readme_benchmarks::Progress::stars)
+        .sum();
+
+    match readme_benchmarks::update_progress(&progress) {
+        Ok(()) => println!("Successfully updated README with progress ({stars}/50 stars)."),
+        Err(_) => {
+            eprintln!("Failed to update readme with progress.");
+            process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,156 @@
+/// Checks solutions against the example manifest of a day, see [`crate::solution`].
+///
+/// A manifest lives next to the examples as `data/examples/DD.manifest` and lists one example
+/// file per line with the expected answer for each part:
+///
+/// ```text
+/// # file       part 1   part 2
+/// 01.txt       142      142
+/// 01-2.txt     none     281
+/// ```
+///
+/// `-` leaves a part unchecked and `none` expects the part to return [`None`].
+/// Lines starting with `#` are comments.
+use crate::parse::{ParseError, Span};
+use crate::template::runner::StrVariant;
+use crate::Day;
+use std::fmt::Display;
+use std::{env, fs};
+
+/// What a part should return for an example.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expected {
+    Unchecked,
+    NoAnswer,
+    Answer(String),
+}
+
+/// An example file and the answers expected for it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Example {
+    pub file: String,
+    pub answers: [Expected; 2],
+}
+
+/// Parses the text of a manifest.
+pub fn parse_manifest(s: &str) -> Result<Vec<Example>, ParseError> {
+    Span::new(s)
+        .lines()
+        .filter(|l| !l.trim().is_empty() && !l.trim().as_str().starts_with('#'))
+        .map(|line| {
+            let fields: Vec<_> = line.split_whitespace().collect();
+            let [file, one, two] = fields[..] else {
+                return Err(line.error("expecting a file and the answers of both parts"));
+            };
+            let expected = |span: Span| match span.as_str() {
+                "-" => Expected::Unchecked,
+                "none" => Expected::NoAnswer,
+                answer => Expected::Answer(answer.to_string()),
+            };
+            Ok(Example {
+                file: file.as_str().to_string(),
+                answers: [expected(one), expected(two)],
+            })
+        })
+        .collect()
+}
+
+/// Reads the manifest of `day`, or [`None`] if the day has none.
+///
+/// # Panics
+/// Panics with the location of the error if the manifest is malformed.
+pub fn read_manifest(day: Day) -> Option<Vec<Example>> {
+    let path = examples_dir().join(format!("{day}.manifest"));
+    let manifest = fs::read_to_string(&path).ok()?;
+    match parse_manifest(&manifest) {
+        Ok(examples) => Some(examples),
+        Err(err) => panic!("invalid manifest {}:\n{err}", path.display()),
+    }
+}
+
+fn examples_dir() -> std::path::PathBuf {
+    env::current_dir().unwrap().join("data").join("examples")
+}
+
+/// Runs every implementation of a part on each example of `day` that has an answer for it.
+///
+/// # Panics
+/// Panics listing every mismatch, or if an example file cannot be read.
+pub fn check_part<T: Display>(day: Day, part: u8, variants: &[StrVariant<T>]) {
+    let Some(examples) = read_manifest(day) else {
+        eprintln!("Day {day} has no example manifest, skipping its examples.");
+        return;
+    };
+
+    let mut failures = vec![];
+    for example in &examples {
+        let expected = &example.answers[usize::from(part - 1)];
+        if *expected == Expected::Unchecked {
+            continue;
+        }
+        let path = examples_dir().join(&example.file);
+        let input = fs::read_to_string(&path)
+            .unwrap_or_else(|e| panic!("could not open example {}: {e}", path.display()));
+
+        for (name, func) in variants {
+            let answer = match func(&input) {
+                Some(result) => Expected::Answer(result.to_string()),
+                None => Expected::NoAnswer,
+            };
+            if answer != *expected {
+                failures.push(format!(
+                    "{}, part {part}, {name}: expected {}, got {}",
+                    example.file,
+                    describe(expected),
+                    describe(&answer)
+                ));
+            }
+        }
+    }
+
+    assert!(
+        failures.is_empty(),
+        "{} example(s) failed:\n{}",
+        failures.len(),
+        failures.join("\n")
+    );
+}
+
+fn describe(expected: &Expected) -> &str {
+    match expected {
+        Expected::Unchecked => "-",
+        Expected::NoAnswer => "none",
+        Expected::Answer(answer) => answer,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{parse_manifest, Example, Expected};
+
+    #[test]
+    fn parses_manifests() {
+        let manifest = "# file  one  two\n01.txt  142  -\n\n01-2.txt  none  281\n";
+        assert_eq!(
+            parse_manifest(manifest).unwrap(),
+            vec![
+                Example {
+                    file: "01.txt".into(),
+                    answers: [Expected::Answer("142".into()), Expected::Unchecked],
+                },
+                Example {
+                    file: "01-2.txt".into(),
+                    answers: [Expected::NoAnswer, Expected::Answer("281".into())],
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn reports_malformed_lines() {
+        let err = parse_manifest("01.txt 142 -\n01-2.txt 281\n").unwrap_err();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+}
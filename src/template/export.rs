@@ -0,0 +1,270 @@
+/// Module that exports benchmark results to machine-readable files.
+/// Unlike `readme_benchmarks`, this keeps the full sample and spread data of every part.
+use std::{fmt::Write as _, fs, io, str::FromStr};
+
+use super::readme_benchmarks::{get_path_for_bin, PartTimings, Timings};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Csv,
+    Markdown,
+}
+
+/// An error which can be returned when parsing a [`Format`].
+#[derive(Debug)]
+pub struct FormatFromStrError;
+
+impl std::error::Error for FormatFromStrError {}
+
+impl std::fmt::Display for FormatFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting one of `json`, `csv` or `markdown`")
+    }
+}
+
+impl FromStr for Format {
+    type Err = FormatFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(Self::Json),
+            "csv" => Ok(Self::Csv),
+            "markdown" | "md" => Ok(Self::Markdown),
+            _ => Err(FormatFromStrError),
+        }
+    }
+}
+
+fn parts(timing: &Timings) -> impl Iterator<Item = (u8, &PartTimings)> {
+    [(1, &timing.part_1), (2, &timing.part_2)]
+        .into_iter()
+        .filter_map(|(part, p)| p.as_ref().map(|p| (part, p)))
+}
+
+fn json_number(n: f64) -> String {
+    if n.is_finite() {
+        n.to_string()
+    } else {
+        "null".into()
+    }
+}
+
+fn to_json(timings: &[Timings], total_millis: f64) -> String {
+    let days: Vec<String> = timings
+        .iter()
+        .map(|timing| {
+            let parts: Vec<String> = parts(timing)
+                .map(|(part, p)| {
+                    let stats = p.stats.map_or_else(
+                        || "null".into(),
+                        |s| {
+                            format!(
+                                r#"{{ "min_nanos": {}, "median_nanos": {}, "max_nanos": {}, "stddev_nanos": {} }}"#,
+                                json_number(s.min_nanos),
+                                json_number(s.median_nanos),
+                                json_number(s.max_nanos),
+                                json_number(s.stddev_nanos)
+                            )
+                        },
+                    );
+                    format!(
+                        r#"        {{ "part": {part}, "timing": "{}", "mean_nanos": {}, "samples": {}, "stats": {stats} }}"#,
+                        p.timing,
+                        json_number(p.mean_nanos),
+                        p.samples
+                    )
+                })
+                .collect();
+
+            format!(
+                "    {{\n      \"day\": {},\n      \"total_nanos\": {},\n      \"parts\": [\n{}\n      ]\n    }}",
+                timing.day.into_inner(),
+                json_number(timing.total_nanos),
+                parts.join(",\n")
+            )
+        })
+        .collect();
+
+    format!(
+        "{{\n  \"total_millis\": {},\n  \"days\": [\n{}\n  ]\n}}\n",
+        json_number(total_millis),
+        days.join(",\n")
+    )
+}
+
+fn to_csv(timings: &[Timings]) -> String {
+    let mut s = String::from(
+        "day,part,timing,mean_nanos,samples,min_nanos,median_nanos,max_nanos,stddev_nanos\n",
+    );
+
+    for timing in timings {
+        for (part, p) in parts(timing) {
+            let stats = p.stats.map_or_else(
+                || ",,,".into(),
+                |s| {
+                    format!(
+                        "{},{},{},{}",
+                        s.min_nanos, s.median_nanos, s.max_nanos, s.stddev_nanos
+                    )
+                },
+            );
+            let _ = writeln!(
+                s,
+                "{},{part},{},{},{},{stats}",
+                timing.day.into_inner(),
+                p.timing,
+                p.mean_nanos,
+                p.samples
+            );
+        }
+    }
+
+    s
+}
+
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", std::time::Duration::from_secs_f64(nanos / 1e9))
+}
+
+fn to_markdown(timings: &[Timings], total_millis: f64) -> String {
+    let mut lines: Vec<String> = vec![
+        "| Day | Part | Mean | Samples | Min | Median | Max | Std. dev. |".into(),
+        "| :---: | :---: | :---: | :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for timing in timings {
+        for (part, p) in parts(timing) {
+            let stats = p.stats.map_or_else(
+                || "- | - | - | -".into(),
+                |s| {
+                    format!(
+                        "`{}` | `{}` | `{}` | `{}`",
+                        format_nanos(s.min_nanos),
+                        format_nanos(s.median_nanos),
+                        format_nanos(s.max_nanos),
+                        format_nanos(s.stddev_nanos)
+                    )
+                },
+            );
+            lines.push(format!(
+                "| [Day {}]({}) | {part} | `{}` | {} | {stats} |",
+                timing.day.into_inner(),
+                get_path_for_bin(timing.day),
+                p.timing,
+                p.samples
+            ));
+        }
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Total: {total_millis:.2}ms**"));
+    lines.push(String::new());
+
+    lines.join("\n")
+}
+
+#[must_use]
+pub fn render(format: Format, timings: &[Timings], total_millis: f64) -> String {
+    match format {
+        Format::Json => to_json(timings, total_millis),
+        Format::Csv => to_csv(timings),
+        Format::Markdown => to_markdown(timings, total_millis),
+    }
+}
+
+/// Writes the rendered timings to `path`, or to stdout if no path is given.
+pub fn export(
+    format: Format,
+    path: Option<&str>,
+    timings: &[Timings],
+    total_millis: f64,
+) -> Result<(), io::Error> {
+    let content = render(format, timings, total_millis);
+    match path {
+        Some(path) => fs::write(path, content),
+        None => {
+            print!("{content}");
+            Ok(())
+        }
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{render, Format};
+    use crate::day;
+    use crate::template::readme_benchmarks::{PartTimings, Stats, Timings};
+
+    fn get_mock_timings() -> Vec<Timings> {
+        vec![
+            Timings {
+                day: day!(1),
+                part_1: Some(PartTimings {
+                    timing: "10.0µs".into(),
+                    mean_nanos: 10000.0,
+                    samples: 100,
+                    stats: Some(Stats {
+                        min_nanos: 9000.0,
+                        median_nanos: 10000.0,
+                        max_nanos: 12000.0,
+                        stddev_nanos: 500.0,
+                    }),
+                }),
+                part_2: None,
+                total_nanos: 10000.0,
+            },
+            Timings {
+                day: day!(3),
+                part_1: Some(PartTimings::new("1.0ms", 1e6, 10)),
+                part_2: Some(PartTimings::new("2.0ms", 2e6, 10)),
+                total_nanos: 3e6,
+            },
+        ]
+    }
+
+    #[test]
+    fn parses_formats() {
+        assert_eq!("json".parse::<Format>().unwrap(), Format::Json);
+        assert_eq!("csv".parse::<Format>().unwrap(), Format::Csv);
+        assert_eq!("markdown".parse::<Format>().unwrap(), Format::Markdown);
+        assert!("yaml".parse::<Format>().is_err());
+    }
+
+    #[test]
+    fn exports_csv() {
+        let csv = render(Format::Csv, &get_mock_timings(), 3.01);
+        let expected = [
+            "day,part,timing,mean_nanos,samples,min_nanos,median_nanos,max_nanos,stddev_nanos",
+            "1,1,10.0µs,10000,100,9000,10000,12000,500",
+            "3,1,1.0ms,1000000,10,,,,",
+            "3,2,2.0ms,2000000,10,,,,",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(csv, expected);
+    }
+
+    #[test]
+    fn exports_json() {
+        let json = render(Format::Json, &get_mock_timings(), 3.01);
+        assert!(json.starts_with("{\n  \"total_millis\": 3.01,"));
+        assert!(json.contains(r#""day": 3"#));
+        assert!(json.contains(
+            r#"{ "part": 1, "timing": "10.0µs", "mean_nanos": 10000, "samples": 100, "stats": { "min_nanos": 9000, "median_nanos": 10000, "max_nanos": 12000, "stddev_nanos": 500 } }"#
+        ));
+        assert!(json.contains(
+            r#"{ "part": 2, "timing": "2.0ms", "mean_nanos": 2000000, "samples": 10, "stats": null }"#
+        ));
+    }
+
+    #[test]
+    fn exports_markdown() {
+        let md = render(Format::Markdown, &get_mock_timings(), 3.01);
+        assert!(md.contains(
+            "| [Day 1](./src/bin/01.rs) | 1 | `10.0µs` | 100 | `9.0µs` | `10.0µs` | `12.0µs` | `500.0ns` |"
+        ));
+        assert!(md.contains("| [Day 3](./src/bin/03.rs) | 2 | `2.0ms` | 10 | - | - | - | - |"));
+        assert!(md.contains("**Total: 3.01ms**"));
+    }
+}
@@ -1,13 +1,22 @@
 use crate::Day;
 use std::{env, fs};
 
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod examples;
+pub mod export;
 pub mod readme_benchmarks;
 pub mod runner;
+pub mod scale;
+pub mod viz;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
+pub const ANSI_DIM: &str = "\x1b[2m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
+pub const ANSI_YELLOW: &str = "\x1b[33m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
@@ -32,17 +41,119 @@ pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
 }
 
 /// Creates the constant `DAY` and sets up the input and runner for each part.
+///
+/// A part can list several competing implementations, e.g. `solution!(5, part_two = [naive, range_split])`.
+/// The runner then checks that all of them agree on the answer and benchmarks them side by side.
+/// The first implementation listed is the one that is submitted and reported to `cargo all`.
+///
+/// Passing `scale = generate_input` registers a `fn(usize) -> String` input generator for `cargo scale`.
+///
+/// Passing `visualize = visualize` registers a `fn(&str) -> String` that renders the day's state
+/// for the real input, printed by `cargo solve <day> --viz` instead of running the parts.
+///
+/// Passing `dot = graph` registers a `fn(&str) -> Dot` for days whose input is a graph, written
+/// as a Graphviz file by `cargo solve <day> --dot FILE`.
+///
+/// Every implementation of both parts is also tested against the examples listed in the day's
+/// manifest, `data/examples/DD.manifest`, see [`examples`].
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]
+        part_one = [$($new:ident),+ $(,)?] $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [$($new),+] [$($two),+] [$($scale)?] [$($viz)?] [$($dot)?] $($($rest)*)?);
+    };
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]
+        part_two = [$($new:ident),+ $(,)?] $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [$($one),+] [$($new),+] [$($scale)?] [$($viz)?] [$($dot)?] $($($rest)*)?);
+    };
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]
+        scale = $new:ident $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [$($one),+] [$($two),+] [$new] [$($viz)?] [$($dot)?] $($($rest)*)?);
+    };
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]
+        visualize = $new:ident $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [$($one),+] [$($two),+] [$($scale)?] [$new] [$($dot)?] $($($rest)*)?);
+    };
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]
+        dot = $new:ident $(, $($rest:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [$($one),+] [$($two),+] [$($scale)?] [$($viz)?] [$new] $($($rest)*)?);
+    };
+    (@munch [$day:expr] [$($one:ident),+] [$($two:ident),+] [$($scale:ident)?] [$($viz:ident)?] [$($dot:ident)?]) => {
         /// The current day.
         const DAY: advent_of_code::Day = advent_of_code::day!($day);
 
+        #[global_allocator]
+        static ALLOCATOR: advent_of_code::template::alloc::CountingAllocator =
+            advent_of_code::template::alloc::CountingAllocator;
+
         fn main() {
             use advent_of_code::template::runner::*;
+
+            if std::env::args().any(|x| x == "--scale") {
+                use advent_of_code::template::scale::*;
+                let generators: &[fn(usize) -> String] = &[$($scale)?];
+                let Some(generator) = generators.first() else {
+                    missing_generator(DAY);
+                    std::process::exit(1);
+                };
+                run_scale(&[$((stringify!($one), &$one)),+], *generator, 1);
+                run_scale(&[$((stringify!($two), &$two)),+], *generator, 2);
+                return;
+            }
+
+            if std::env::args().any(|x| x == "--viz") {
+                let visualizers: &[fn(&str) -> String] = &[$($viz)?];
+                let Some(visualize) = visualizers.first() else {
+                    advent_of_code::template::viz::missing_visualizer(DAY);
+                    std::process::exit(1);
+                };
+                let input = advent_of_code::template::read_file("inputs", DAY);
+                print!("{}", visualize(&input));
+                return;
+            }
+
+            if let Some(path) = advent_of_code::template::viz::dot_path() {
+                let graphs: &[fn(&str) -> advent_of_code::dot::Dot] = &[$($dot)?];
+                let Some(graph) = graphs.first() else {
+                    advent_of_code::template::viz::missing_graph(DAY);
+                    std::process::exit(1);
+                };
+                let input = advent_of_code::template::read_file("inputs", DAY);
+                advent_of_code::template::viz::write_dot(&path, &graph(&input));
+                return;
+            }
+
             let input = advent_of_code::template::read_file("inputs", DAY);
-            run_part(part_one, &input, DAY, 1);
-            run_part(part_two, &input, DAY, 2);
+
+            run_variants(&[$((stringify!($one), &$one)),+], &input, DAY, 1);
+            run_variants(&[$((stringify!($two), &$two)),+], &input, DAY, 2);
+        }
+
+        #[cfg(test)]
+        mod example_manifest {
+            #[test]
+            fn part_one_examples() {
+                advent_of_code::template::examples::check_part(
+                    super::DAY,
+                    1,
+                    &[$((stringify!($one), &super::$one)),+],
+                );
+            }
+
+            #[test]
+            fn part_two_examples() {
+                advent_of_code::template::examples::check_part(
+                    super::DAY,
+                    2,
+                    &[$((stringify!($two), &super::$two)),+],
+                );
+            }
         }
     };
+    (@munch [$day:expr] $one:tt $two:tt $scale:tt $viz:tt $dot:tt $key:ident $($rest:tt)*) => {
+        compile_error!(concat!("unknown `solution!` option `", stringify!($key), "`"));
+    };
+    ($day:expr $(, $($options:tt)*)?) => {
+        advent_of_code::solution!(@munch [$day] [part_one] [part_two] [] [] [] $($($options)*)?);
+    };
 }
@@ -1,10 +1,11 @@
-/// Module that updates the readme me with timing information.
+/// Module that updates the readme me with timing information and a progress calendar.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path};
 
-use crate::Day;
+use crate::{all_days, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
+static PROGRESS_MARKER: &str = "<!--- progress calendar --->";
 
 #[derive(Debug)]
 pub enum Error {
@@ -21,11 +22,59 @@ impl From<std::io::Error> for Error {
 #[derive(Clone)]
 pub struct Timings {
     pub day: Day,
-    pub part_1: Option<String>,
-    pub part_2: Option<String>,
+    pub part_1: Option<PartTimings>,
+    pub part_2: Option<PartTimings>,
     pub total_nanos: f64,
 }
 
+/// Benchmark result for a single part, as reported by the solution bin.
+#[derive(Clone)]
+pub struct PartTimings {
+    /// The average execution time, formatted for display. E.g. `74.1µs`.
+    pub timing: String,
+    pub mean_nanos: f64,
+    pub samples: u128,
+    pub stats: Option<Stats>,
+}
+
+impl PartTimings {
+    #[must_use]
+    pub fn new(timing: &str, mean_nanos: f64, samples: u128) -> Self {
+        Self {
+            timing: timing.into(),
+            mean_nanos,
+            samples,
+            stats: None,
+        }
+    }
+}
+
+/// Spread of the sample timings of a part, in nanoseconds.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min_nanos: f64,
+    pub median_nanos: f64,
+    pub max_nanos: f64,
+    pub stddev_nanos: f64,
+}
+
+/// Stars earned for a single day. A part counts as solved once the day has a
+/// solution bin and an answer for the part is stored in `data/answers/{day}.txt`.
+#[derive(Clone)]
+pub struct Progress {
+    pub day: Day,
+    pub has_bin: bool,
+    pub part_1: bool,
+    pub part_2: bool,
+}
+
+impl Progress {
+    #[must_use]
+    pub fn stars(&self) -> usize {
+        usize::from(self.part_1) + usize::from(self.part_2)
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
@@ -36,8 +85,51 @@ pub fn get_path_for_bin(day: Day) -> String {
     format!("./src/bin/{day}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+#[must_use]
+pub fn get_path_for_answers(day: Day) -> String {
+    format!("./data/answers/{day}.txt")
+}
+
+/// Stores the answer to one part of a day, keeping the answer to the other part.
+pub fn store_answer(day: Day, part: u8, answer: &str) -> Result<(), Error> {
+    let path = get_path_for_answers(day);
+    let stored = fs::read_to_string(&path).unwrap_or_default();
+    fs::write(&path, with_answer(&stored, part, answer))?;
+    Ok(())
+}
+
+/// Replaces the line holding the answer to `part`, padding missing parts with blank lines.
+fn with_answer(stored: &str, part: u8, answer: &str) -> String {
+    let mut lines: Vec<&str> = stored.lines().collect();
+    let index = usize::from(part.saturating_sub(1));
+    if lines.len() <= index {
+        lines.resize(index + 1, "");
+    }
+    lines[index] = answer;
+    lines.join("\n") + "\n"
+}
+
+/// Reads the stored answers for every day. The answers file holds one answer per line,
+/// part 1 first. Blank lines mark parts that are not solved yet.
+#[must_use]
+pub fn get_progress() -> Vec<Progress> {
+    all_days()
+        .map(|day| {
+            let has_bin = Path::new(&get_path_for_bin(day)).exists();
+            let answers = fs::read_to_string(get_path_for_answers(day)).unwrap_or_default();
+            let mut lines = answers.lines().map(|l| !l.trim().is_empty());
+            Progress {
+                day,
+                has_bin,
+                part_1: has_bin && lines.next().unwrap_or(false),
+                part_2: has_bin && lines.next().unwrap_or(false),
+            }
+        })
+        .collect()
+}
+
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -75,8 +167,8 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
             "| [Day {}]({}) | `{}` | `{}` |",
             timing.day.into_inner(),
             path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
+            timing.part_1.map_or_else(|| "-".into(), |p| p.timing),
+            timing.part_2.map_or_else(|| "-".into(), |p| p.timing)
         ));
     }
 
@@ -87,13 +179,60 @@ fn construct_table(prefix: &str, timings: Vec<Timings>, total_millis: f64) -> St
     lines.join("\n")
 }
 
+fn format_stars(progress: &Progress) -> String {
+    let star = |solved| if solved { "⭐" } else { "☆" };
+    let day = progress.day.into_inner();
+
+    if progress.has_bin {
+        format!(
+            "[{day}]({}) {}{}",
+            get_path_for_bin(progress.day),
+            star(progress.part_1),
+            star(progress.part_2)
+        )
+    } else {
+        format!("{day} {}{}", star(false), star(false))
+    }
+}
+
+fn construct_calendar(prefix: &str, progress: &[Progress]) -> String {
+    let total_stars: usize = progress.iter().map(Progress::stars).sum();
+    let header = format!("{prefix} Progress");
+
+    let mut lines: Vec<String> = vec![
+        PROGRESS_MARKER.into(),
+        header,
+        String::new(),
+        "| | | | | |".into(),
+        "| :---: | :---: | :---: | :---: | :---: |".into(),
+    ];
+
+    for week in progress.chunks(5) {
+        let cells: Vec<String> = week.iter().map(format_stars).collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
+    }
+
+    lines.push(String::new());
+    lines.push(format!("**Stars: {total_stars}/50**"));
+    lines.push(PROGRESS_MARKER.into());
+
+    lines.join("\n")
+}
+
 fn update_content(s: &mut String, timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
+    let positions = locate_table(s, MARKER)?;
     let table = construct_table("##", timings, total_millis);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
+fn update_progress_content(s: &mut String, progress: &[Progress]) -> Result<(), Error> {
+    let positions = locate_table(s, PROGRESS_MARKER)?;
+    let calendar = construct_calendar("##", progress);
+    s.replace_range(positions.pos_start..positions.pos_end, &calendar);
+    Ok(())
+}
+
 pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     let path = "README.md";
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
@@ -102,29 +241,40 @@ pub fn update(timings: Vec<Timings>, total_millis: f64) -> Result<(), Error> {
     Ok(())
 }
 
+pub fn update_progress(progress: &[Progress]) -> Result<(), Error> {
+    let path = "README.md";
+    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+    update_progress_content(&mut readme, progress)?;
+    fs::write(path, &readme)?;
+    Ok(())
+}
+
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, Timings, MARKER};
-    use crate::day;
+    use super::{
+        update_content, update_progress_content, with_answer, PartTimings, Progress, Timings,
+        MARKER, PROGRESS_MARKER,
+    };
+    use crate::{all_days, day};
 
     fn get_mock_timings() -> Vec<Timings> {
         vec![
             Timings {
                 day: day!(1),
-                part_1: Some("10ms".into()),
-                part_2: Some("20ms".into()),
+                part_1: Some(PartTimings::new("10ms", 1e+7, 10)),
+                part_2: Some(PartTimings::new("20ms", 2e+7, 10)),
                 total_nanos: 3e+10,
             },
             Timings {
                 day: day!(2),
-                part_1: Some("30ms".into()),
-                part_2: Some("40ms".into()),
+                part_1: Some(PartTimings::new("30ms", 3e+7, 10)),
+                part_2: Some(PartTimings::new("40ms", 4e+7, 10)),
                 total_nanos: 7e+10,
             },
             Timings {
                 day: day!(4),
-                part_1: Some("40ms".into()),
-                part_2: Some("50ms".into()),
+                part_1: Some(PartTimings::new("40ms", 4e+7, 10)),
+                part_2: Some(PartTimings::new("50ms", 5e+7, 10)),
                 total_nanos: 9e+10,
             },
         ]
@@ -183,4 +333,67 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    fn get_mock_progress() -> Vec<Progress> {
+        all_days()
+            .map(|day| Progress {
+                day,
+                has_bin: day <= 3,
+                part_1: day <= 3,
+                part_2: day <= 2,
+            })
+            .collect()
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_if_progress_marker_not_present() {
+        let mut s = format!("foo\n{}{}\nbaz", MARKER, MARKER);
+        update_progress_content(&mut s, &get_mock_progress()).unwrap();
+    }
+
+    #[test]
+    fn updates_progress_next_to_benchmarks() {
+        let mut s = format!("foo\n{}\n{}\nbaz", PROGRESS_MARKER, MARKER);
+        update_progress_content(&mut s, &get_mock_progress()).unwrap();
+        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_progress_content(&mut s, &get_mock_progress()).unwrap();
+        assert_eq!(s.matches(PROGRESS_MARKER).count(), 2);
+        assert_eq!(s.matches(MARKER).count(), 2);
+        assert_eq!(s.matches("## Progress").count(), 1);
+        assert_eq!(s.matches("## Benchmarks").count(), 1);
+    }
+
+    #[test]
+    fn format_progress() {
+        let mut s = format!("foo\n{}\nbar", PROGRESS_MARKER);
+        update_progress_content(&mut s, &get_mock_progress()).unwrap();
+        let expected = [
+            "foo",
+            "<!--- progress calendar --->",
+            "## Progress",
+            "",
+            "| | | | | |",
+            "| :---: | :---: | :---: | :---: | :---: |",
+            "| [1](./src/bin/01.rs) ⭐⭐ | [2](./src/bin/02.rs) ⭐⭐ | [3](./src/bin/03.rs) ⭐☆ | 4 ☆☆ | 5 ☆☆ |",
+            "| 6 ☆☆ | 7 ☆☆ | 8 ☆☆ | 9 ☆☆ | 10 ☆☆ |",
+            "| 11 ☆☆ | 12 ☆☆ | 13 ☆☆ | 14 ☆☆ | 15 ☆☆ |",
+            "| 16 ☆☆ | 17 ☆☆ | 18 ☆☆ | 19 ☆☆ | 20 ☆☆ |",
+            "| 21 ☆☆ | 22 ☆☆ | 23 ☆☆ | 24 ☆☆ | 25 ☆☆ |",
+            "",
+            "**Stars: 5/50**",
+            "<!--- progress calendar --->",
+            "bar",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn stores_answers_per_part() {
+        assert_eq!(with_answer("", 1, "142"), "142\n");
+        assert_eq!(with_answer("", 2, "281"), "\n281\n");
+        assert_eq!(with_answer("\n281\n", 1, "142"), "142\n281\n");
+        assert_eq!(with_answer("142\n0\n", 2, "281"), "142\n281\n");
+    }
 }
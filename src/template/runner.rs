@@ -1,63 +1,246 @@
 /// Encapsulates code that interacts with solution functions.
-use crate::template::{aoc_cli, ANSI_ITALIC, ANSI_RESET};
+use crate::memo::{self, MemoStats};
+use crate::template::alloc::{self, format_bytes, AllocStats};
+use crate::template::{aoc_cli, readme_benchmarks, ANSI_ITALIC, ANSI_RESET};
 use crate::Day;
 use std::fmt::Display;
 use std::io::{stdout, Write};
 use std::process::Output;
+use std::sync::Mutex;
 use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use super::ANSI_BOLD;
 
+/// The first error reported by the part that is currently running, see [`report_error`].
+static REPORTED_ERROR: Mutex<Option<String>> = Mutex::new(None);
+
+/// Records why a part has no answer, e.g. a parse error pointing at the input.
+///
+/// The runner prints the error of the first execution once, after the part has run, so that
+/// benching with `--time` does not repeat it for every sample. Only the first error of an
+/// execution is kept.
+pub fn report_error(error: impl Display) {
+    if let Ok(mut reported) = REPORTED_ERROR.lock() {
+        reported.get_or_insert_with(|| error.to_string());
+    }
+}
+
+fn take_reported_error() -> Option<String> {
+    REPORTED_ERROR.lock().ok()?.take()
+}
+
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let run = run_timed(func, input, |result| print_result(result, &part_str, ""));
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    print_result(
+        &run.result,
+        &part_str,
+        &format_duration(&run.duration, run.samples),
+    );
+    print_run_details(&run, &part_str);
 
-    if let Some(result) = result {
+    if let Some(result) = run.result {
         submit_result(result, day, part);
     }
 }
 
+/// A named implementation of a solution part.
+pub type Variant<'a, I, T> = (&'a str, &'a dyn Fn(I) -> Option<T>);
+
+/// A named implementation of a solution part that accepts inputs of any lifetime, for inputs
+/// that are read or generated after the implementations are listed.
+pub type StrVariant<'a, T> = (&'a str, &'a dyn Fn(&str) -> Option<T>);
+
+/// Outcome of running a solution part, including the measurements taken along the way.
+struct Run<T> {
+    result: T,
+    duration: Duration,
+    samples: u128,
+    /// Individual sample timings, empty unless benched.
+    timers: Vec<Duration>,
+    /// Allocations of the first execution, only counted with `--allocs`.
+    allocs: Option<AllocStats>,
+    /// Cache lookups of the first execution, only reported with `--time` if there were any.
+    memo: Option<MemoStats>,
+    /// The error reported by the first execution, if any.
+    error: Option<String>,
+}
+
+/// Run every implementation of a solution part and compare them side by side.
+/// With a single implementation, this behaves exactly like [`run_part`].
+pub fn run_variants<I: Clone, T: Display + PartialEq>(
+    variants: &[Variant<I, T>],
+    input: I,
+    day: Day,
+    part: u8,
+) {
+    if let [(_, func)] = variants {
+        run_part(func, input, day, part);
+        return;
+    }
+
+    let part_str = format!("Part {part}");
+
+    let runs: Vec<_> = variants
+        .iter()
+        .map(|(name, func)| {
+            let run = run_timed(func, input.clone(), |_| {
+                print!("\r{part_str}: {ANSI_ITALIC}{name}{ANSI_RESET}");
+            });
+            print!("\r");
+            (*name, run)
+        })
+        .collect();
+
+    let (_, first) = &runs[0];
+    let agree = runs.iter().all(|(_, run)| run.result == first.result);
+
+    if agree {
+        print_result(
+            &first.result,
+            &part_str,
+            &format_duration(&first.duration, first.samples),
+        );
+        print_run_details(first, &part_str);
+    } else {
+        println!("{part_str}: ✖ implementations disagree             ");
+    }
+
+    print_variants_table(&runs, agree);
+
+    if !agree {
+        eprintln!("Not submitting part {part}: implementations returned different answers.");
+        return;
+    }
+
+    if let Some(result) = runs.into_iter().next().and_then(|(_, run)| run.result) {
+        submit_result(result, day, part);
+    }
+}
+
+/// Prints the reported error, sample stats, allocation counts and cache lookups of a run, if any.
+/// `cargo all` parses these lines for exports and its summary.
+fn print_run_details<T>(run: &Run<T>, part_str: &str) {
+    if let Some(error) = &run.error {
+        eprintln!("{error}");
+    }
+
+    if !run.timers.is_empty() {
+        println!("{part_str} stats: {}", format_stats(&run.timers));
+    }
+
+    if let Some(allocs) = run.allocs {
+        println!("{part_str} allocs: {}", format_allocs(&allocs));
+    }
+
+    if let Some(memo) = run.memo {
+        println!("{part_str} memo: {}", format_memo(&memo));
+    }
+}
+
+/// Prints one row per implementation with its timing relative to the fastest one.
+/// Answers are only listed if the implementations disagree.
+fn print_variants_table<T: Display>(runs: &[(&str, Run<Option<T>>)], agree: bool) {
+    let fastest = runs
+        .iter()
+        .map(|(_, run)| run.duration.as_nanos())
+        .min()
+        .unwrap_or(1)
+        .max(1);
+    let name_width = runs
+        .iter()
+        .map(|(name, _)| name.len())
+        .fold("variant".len(), cmp::max);
+
+    println!(
+        "  {:<name_width$}  {:>10}  {:>9}",
+        "variant", "time", "relative"
+    );
+
+    for (name, run) in runs {
+        #[allow(clippy::cast_precision_loss)]
+        let relative = run.duration.as_nanos() as f64 / fastest as f64;
+        let samples_str = if run.samples > 1 {
+            format!("  @ {} samples", run.samples)
+        } else {
+            String::new()
+        };
+        let allocs_str = run
+            .allocs
+            .map(|allocs| format!("  [{}]", format_allocs(&allocs)))
+            .unwrap_or_default();
+        let result_str = match (agree, &run.result) {
+            (true, _) => String::new(),
+            (false, Some(result)) => format!("  = {result}"),
+            (false, None) => "  = ✖".into(),
+        };
+        println!(
+            "  {name:<name_width$}  {:>10}  {relative:>8.2}x{samples_str}{allocs_str}{result_str}",
+            format!("{:.1?}", run.duration),
+        );
+    }
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
-fn run_timed<I: Clone, T>(
-    func: impl Fn(I) -> T,
-    input: I,
-    hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+///
+/// With `--allocs`, the allocations of the first execution are counted as well.
+/// With `--time`, so are the hits and misses of any [`memo::Memo`] caches.
+fn run_timed<I: Clone, T>(func: impl Fn(I) -> T, input: I, hook: impl Fn(&T)) -> Run<T> {
+    let count_allocs = env::args().any(|x| x == "--allocs");
+    let is_timed = env::args().any(|x| x == "--time");
+
+    memo::reset_stats();
+    take_reported_error();
     let timer = Instant::now();
-    let result = func(input.clone());
+    let (result, allocs) = if count_allocs {
+        let (result, allocs) = alloc::measure(|| func(input.clone()));
+        (result, Some(allocs))
+    } else {
+        (func(input.clone()), None)
+    };
     let base_time = timer.elapsed();
+    let memo = Some(memo::stats()).filter(|m| is_timed && m.hits + m.misses > 0);
+    let error = take_reported_error();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (duration, samples, timers) = if is_timed {
+        let bench = bench(func, input, &base_time);
+        // the samples repeat the first execution's error, if any.
+        take_reported_error();
+        bench
     } else {
-        (base_time, 1)
+        (base_time, 1, vec![])
     };
 
-    (result, run.0, run.1)
+    Run {
+        result,
+        duration,
+        samples,
+        timers,
+        allocs,
+        memo,
+        error,
+    }
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+) -> (Duration, u128, Vec<Duration>) {
     let mut stdout = stdout();
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+    let bench_iterations =
+        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -73,6 +256,7 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         #[allow(clippy::cast_possible_truncation)]
         Duration::from_nanos(average_duration(&timers) as u64),
         bench_iterations,
+        timers,
     )
 }
 
@@ -84,6 +268,48 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+/// Formats min, median, max and standard deviation of the sample timings.
+/// `cargo all` parses this line to collect the stats for exports.
+fn format_stats(timers: &[Duration]) -> String {
+    let mut sorted = timers.to_vec();
+    sorted.sort();
+
+    #[allow(clippy::cast_precision_loss)]
+    let mean = average_duration(&sorted) as f64;
+    #[allow(clippy::cast_precision_loss)]
+    let variance = sorted
+        .iter()
+        .map(|d| (d.as_nanos() as f64 - mean).powi(2))
+        .sum::<f64>()
+        / sorted.len() as f64;
+    let stddev = Duration::from_secs_f64(variance.sqrt() / 1e9);
+
+    format!(
+        "min {:.1?}, median {:.1?}, max {:.1?}, stddev {stddev:.1?}",
+        sorted[0],
+        sorted[sorted.len() / 2],
+        sorted[sorted.len() - 1],
+    )
+}
+
+fn format_allocs(allocs: &AllocStats) -> String {
+    format!(
+        "{} allocations, {} allocated, {} peak",
+        allocs.allocations,
+        format_bytes(allocs.bytes),
+        format_bytes(allocs.peak_bytes)
+    )
+}
+
+fn format_memo(memo: &MemoStats) -> String {
+    format!(
+        "{} hits, {} misses ({:.1}% hit rate)",
+        memo.hits,
+        memo.misses,
+        memo.hit_rate() * 100.0
+    )
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -163,5 +389,17 @@ fn submit_result<T: Display>(
     }
 
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    let answer = result.to_string();
+    let submission = aoc_cli::submit(day, part, &answer);
+
+    if submission.as_ref().is_ok_and(aoc_cli::is_right_answer)
+        && readme_benchmarks::store_answer(day, part, &answer).is_err()
+    {
+        eprintln!(
+            "Could not store the answer in \"{}\".",
+            readme_benchmarks::get_path_for_answers(day)
+        );
+    }
+
+    Some(submission)
 }
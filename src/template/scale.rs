@@ -0,0 +1,252 @@
+/// Estimates the growth order of a solution by timing it on generated inputs of increasing size.
+use std::fmt::Display;
+use std::hint::black_box;
+use std::io::{stdout, Write};
+use std::time::{Duration, Instant};
+
+use super::runner::StrVariant;
+use super::{ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::Day;
+
+/// The ladder of input sizes starts here and doubles on every step.
+const MIN_SIZE: usize = 16;
+const MAX_STEPS: u32 = 17;
+/// Stop climbing the ladder once a single run takes longer than this.
+const MAX_RUN_TIME: Duration = Duration::from_millis(500);
+/// Repeat fast runs until they take this long in total to smooth out noise.
+const MIN_SAMPLE_TIME: Duration = Duration::from_millis(20);
+const MAX_SAMPLE_RUNS: u32 = 1000;
+
+/// Average execution time for one input size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Sample {
+    pub size: usize,
+    pub nanos: f64,
+}
+
+/// Growth orders the measurements are compared against.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Growth {
+    Constant,
+    Logarithmic,
+    Linear,
+    Linearithmic,
+    Quadratic,
+    Cubic,
+    Exponential,
+}
+
+impl Growth {
+    const ALL: [Growth; 7] = [
+        Growth::Constant,
+        Growth::Logarithmic,
+        Growth::Linear,
+        Growth::Linearithmic,
+        Growth::Quadratic,
+        Growth::Cubic,
+        Growth::Exponential,
+    ];
+
+    /// Natural logarithm of the growth function at `n`.
+    fn ln_at(self, n: f64) -> f64 {
+        match self {
+            Growth::Constant => 0.0,
+            Growth::Logarithmic => n.ln().ln(),
+            Growth::Linear => n.ln(),
+            Growth::Linearithmic => n.ln() + n.ln().ln(),
+            Growth::Quadratic => 2.0 * n.ln(),
+            Growth::Cubic => 3.0 * n.ln(),
+            Growth::Exponential => n * std::f64::consts::LN_2,
+        }
+    }
+}
+
+impl Display for Growth {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Growth::Constant => "O(1)",
+            Growth::Logarithmic => "O(log n)",
+            Growth::Linear => "O(n)",
+            Growth::Linearithmic => "O(n log n)",
+            Growth::Quadratic => "O(n²)",
+            Growth::Cubic => "O(n³)",
+            Growth::Exponential => "O(2ⁿ)",
+        })
+    }
+}
+
+/// Result of fitting the measurements.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Fit {
+    /// The growth order whose ratio `time / f(n)` is the most constant across all samples.
+    pub growth: Growth,
+    /// Slope of the log-log regression, i.e. `k` in `time ~ n^k`.
+    pub exponent: f64,
+}
+
+fn variance(values: &[f64]) -> f64 {
+    #[allow(clippy::cast_precision_loss)]
+    let len = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / len;
+    values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / len
+}
+
+/// Fits the samples against every [`Growth`] and a power law.
+/// Needs at least three samples with sizes above one.
+#[must_use]
+pub fn fit(samples: &[Sample]) -> Option<Fit> {
+    #[allow(clippy::cast_precision_loss)]
+    let points: Vec<(f64, f64)> = samples
+        .iter()
+        .filter(|s| s.size > 1 && s.nanos > 0.0)
+        .map(|s| (s.size as f64, s.nanos))
+        .collect();
+
+    if points.len() < 3 {
+        return None;
+    }
+
+    let ln_n: Vec<f64> = points.iter().map(|(n, _)| n.ln()).collect();
+    let ln_t: Vec<f64> = points.iter().map(|(_, t)| t.ln()).collect();
+
+    #[allow(clippy::cast_precision_loss)]
+    let len = points.len() as f64;
+    let mean_n = ln_n.iter().sum::<f64>() / len;
+    let mean_t = ln_t.iter().sum::<f64>() / len;
+    let covariance: f64 = ln_n
+        .iter()
+        .zip(&ln_t)
+        .map(|(n, t)| (n - mean_n) * (t - mean_t))
+        .sum();
+    let spread: f64 = ln_n.iter().map(|n| (n - mean_n).powi(2)).sum();
+    let exponent = covariance / spread;
+
+    let growth = Growth::ALL
+        .into_iter()
+        .map(|growth| {
+            let ratios: Vec<f64> = points
+                .iter()
+                .zip(&ln_t)
+                .map(|((n, _), t)| t - growth.ln_at(*n))
+                .collect();
+            (growth, variance(&ratios))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(growth, _)| growth)?;
+
+    Some(Fit { growth, exponent })
+}
+
+fn measure<T>(func: &dyn Fn(&str) -> Option<T>, input: &str) -> Duration {
+    let mut runs = 0;
+    let timer = Instant::now();
+
+    while runs < MAX_SAMPLE_RUNS && (runs == 0 || timer.elapsed() < MIN_SAMPLE_TIME) {
+        black_box(func(black_box(input)));
+        runs += 1;
+    }
+
+    timer.elapsed() / runs
+}
+
+/// Times every implementation of a part on a ladder of generated inputs and prints the apparent growth order.
+pub fn run_scale<T>(variants: &[StrVariant<T>], generator: fn(usize) -> String, part: u8) {
+    for (name, func) in variants {
+        if variants.len() > 1 {
+            println!("{ANSI_BOLD}Part {part}{ANSI_RESET} ({name})");
+        } else {
+            println!("{ANSI_BOLD}Part {part}{ANSI_RESET}");
+        }
+        println!("  {:>10}  {:>10}", "size", "time");
+
+        let mut samples = vec![];
+
+        for step in 0..MAX_STEPS {
+            let size = MIN_SIZE << step;
+            let input = generator(size);
+
+            print!("  {size:>10}  {ANSI_ITALIC}running{ANSI_RESET}");
+            let _ = stdout().flush();
+
+            let duration = measure(*func, &input);
+            println!("\r  {size:>10}  {:>10}", format!("{duration:.1?}"));
+
+            #[allow(clippy::cast_precision_loss)]
+            samples.push(Sample {
+                size,
+                nanos: duration.as_nanos() as f64,
+            });
+
+            if duration > MAX_RUN_TIME {
+                break;
+            }
+        }
+
+        match fit(&samples) {
+            Some(fit) => println!(
+                "  apparent growth: {ANSI_BOLD}{}{ANSI_RESET} (time ~ n^{:.2})",
+                fit.growth, fit.exponent
+            ),
+            None => println!("  not enough samples to estimate the growth order."),
+        }
+        println!();
+    }
+}
+
+/// Prints a hint for days that did not register an input generator with `solution!`.
+pub fn missing_generator(day: Day) {
+    eprintln!("Day {day} does not provide an input generator.");
+    eprintln!(
+        "Add a `fn generate_input(size: usize) -> String` and register it with `advent_of_code::solution!({}, scale = generate_input);`.",
+        day.into_inner()
+    );
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{fit, Growth, Sample};
+
+    fn samples(f: impl Fn(f64) -> f64) -> Vec<Sample> {
+        (4..16)
+            .map(|step| {
+                let size = 1usize << step;
+                Sample {
+                    size,
+                    nanos: f(size as f64),
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    fn needs_three_samples() {
+        assert_eq!(fit(&samples(|n| n)[..2]), None);
+    }
+
+    #[test]
+    fn fits_constant() {
+        let fit = fit(&samples(|_| 50.0)).unwrap();
+        assert_eq!(fit.growth, Growth::Constant);
+        assert!(fit.exponent.abs() < 0.01);
+    }
+
+    #[test]
+    fn fits_linear() {
+        let fit = fit(&samples(|n| 3.0 * n + 10.0)).unwrap();
+        assert_eq!(fit.growth, Growth::Linear);
+        assert!((fit.exponent - 1.0).abs() < 0.05);
+    }
+
+    #[test]
+    fn fits_linearithmic() {
+        let fit = fit(&samples(|n| 2.0 * n * n.ln())).unwrap();
+        assert_eq!(fit.growth, Growth::Linearithmic);
+    }
+
+    #[test]
+    fn fits_quadratic() {
+        let fit = fit(&samples(|n| 0.5 * n * n)).unwrap();
+        assert_eq!(fit.growth, Growth::Quadratic);
+        assert!((fit.exponent - 2.0).abs() < 0.01);
+    }
+}
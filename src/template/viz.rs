@@ -0,0 +1,53 @@
+/// Helpers for the optional `visualize` and `dot` functions of a solution, see [`crate::solution`].
+use crate::dot::Dot;
+use crate::template::ANSI_RESET;
+use crate::Day;
+use std::fmt::Display;
+use std::{env, fs, process};
+
+/// Wraps `text` in the given ANSI styles, e.g. `paint(42, &[ANSI_BOLD, ANSI_GREEN])`.
+pub fn paint(text: impl Display, styles: &[&str]) -> String {
+    if styles.is_empty() {
+        text.to_string()
+    } else {
+        format!("{}{text}{ANSI_RESET}", styles.concat())
+    }
+}
+
+/// Prints a hint for days that did not register a visualizer with `solution!`.
+pub fn missing_visualizer(day: Day) {
+    eprintln!("Day {day} does not provide a visualization.");
+    eprintln!(
+        "Add a `fn visualize(input: &str) -> String` and register it with `advent_of_code::solution!({}, visualize = visualize);`.",
+        day.into_inner()
+    );
+}
+
+/// The file passed with `--dot FILE`, if any.
+pub fn dot_path() -> Option<String> {
+    let args: Vec<String> = env::args().collect();
+    let index = args.iter().position(|x| x == "--dot")?;
+    let Some(path) = args.get(index + 1) else {
+        eprintln!("Unexpected command-line input. Format: cargo solve 8 --dot graph.dot");
+        process::exit(1);
+    };
+    Some(path.clone())
+}
+
+/// Writes `dot` to `path`, exiting on failure.
+pub fn write_dot(path: &str, dot: &Dot) {
+    if let Err(e) = fs::write(path, dot.to_string()) {
+        eprintln!("Failed to write graph to \"{path}\": {e}");
+        process::exit(1);
+    }
+    println!("Wrote graph to \"{path}\". Render it with e.g. `dot -Tsvg {path} -o graph.svg`.");
+}
+
+/// Prints a hint for days that did not register a graph with `solution!`.
+pub fn missing_graph(day: Day) {
+    eprintln!("Day {day} does not provide a graph.");
+    eprintln!(
+        "Add a `fn graph(input: &str) -> advent_of_code::dot::Dot` and register it with `advent_of_code::solution!({}, dot = graph);`.",
+        day.into_inner()
+    );
+}